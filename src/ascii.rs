@@ -0,0 +1,238 @@
+use crate::rules::{Board, Player};
+use crate::Game;
+
+/// Amount of checkers drawn on a point before the rest is written as a count
+const STACK: usize = 5;
+
+/// Character of the checkers of a player, `O` for Player0 and `X` for Player1
+fn checker_char(player: Player) -> char {
+    match player {
+        Player::Player1 => 'X',
+        _ => 'O',
+    }
+}
+
+/// Cell of a point or the bar in the given row, counted from the end the checkers stack from
+fn cell(row: usize, checkers: usize, c: char) -> String {
+    if checkers <= row {
+        String::from("   ")
+    } else if row == STACK - 1 && checkers > STACK {
+        format!("{:>2} ", checkers)
+    } else {
+        format!(" {} ", c)
+    }
+}
+
+/// Border of the board with the point numbers
+fn border(labels: &[usize]) -> String {
+    let numbers =
+        |labels: &[usize]| -> String { labels.iter().map(|n| format!("{:->2}-", n)).collect() };
+    format!("+{}-----{}+", numbers(&labels[..6]), numbers(&labels[6..]))
+}
+
+impl Board {
+    /// Draw the board as an ASCII diagram, with the point numbers seen from `player`.
+    ///
+    /// Checkers of Player0 are drawn as `O` and checkers of Player1 as `X`. Points with more than
+    /// five checkers show the amount of checkers on the fifth row. The checkers on the bar are
+    /// drawn in the middle, next to the side of the board they enter, and the checkers off the
+    /// board are counted in the trays on the right. Points are seen from Player0 if `player` is
+    /// nobody.
+    ///
+    /// ```
+    /// use backgammon::rules::{Board, Player};
+    ///
+    /// let diagram = Board::new().to_ascii(Player::Player0);
+    /// assert_eq!(
+    ///     diagram.lines().next(),
+    ///     Some("+12-11-10--9--8--7-------6--5--4--3--2--1-+")
+    /// );
+    /// assert_eq!(
+    ///     diagram.lines().nth(1),
+    ///     Some("| X           O    |   | O              X | +-------+")
+    /// );
+    /// ```
+    pub fn to_ascii(&self, player: Player) -> String {
+        let player = match player {
+            Player::Nobody => Player::Player0,
+            player => player,
+        };
+        let opponent = player.other();
+        // the checkers are always valid for Player0 and Player1
+        let own = self.checkers(player).unwrap_or_default();
+        let other = self.checkers(opponent).unwrap_or_default();
+        let off = (
+            self.off(player).unwrap_or_default(),
+            self.off(opponent).unwrap_or_default(),
+        );
+
+        // checkers on the point with the given number, seen from the player
+        let point = |n: usize| -> (usize, char) {
+            if own[n - 1] > 0 {
+                (own[n - 1] as usize, checker_char(player))
+            } else {
+                (other[24 - n] as usize, checker_char(opponent))
+            }
+        };
+        let row = |row: usize, labels: &[usize], bar: (usize, char)| -> String {
+            let cells = |labels: &[usize]| -> String {
+                labels
+                    .iter()
+                    .map(|&n| {
+                        let (checkers, c) = point(n);
+                        cell(row, checkers, c)
+                    })
+                    .collect()
+            };
+            format!(
+                "|{}|{}|{}|",
+                cells(&labels[..6]),
+                cell(STACK - 1 - row, bar.0, bar.1),
+                cells(&labels[6..])
+            )
+        };
+        let tray = |player: Player, off: u8| -> [String; 4] {
+            [
+                String::from(" +-------+"),
+                format!(" | OFF {} |", checker_char(player)),
+                format!(" |{:^7}|", off),
+                String::from(" +-------+"),
+            ]
+        };
+
+        let top: Vec<usize> = (1..=12).rev().collect();
+        let bottom: Vec<usize> = (13..=24).collect();
+        // the player enters on the bottom side, the opponent on the top side
+        let bar = (
+            (other[24] as usize, checker_char(opponent)),
+            (own[24] as usize, checker_char(player)),
+        );
+
+        let mut lines = vec![border(&top)];
+        lines.extend((0..STACK).map(|r| row(r, &top, bar.0)));
+        lines.push(format!("|{:18}|BAR|{:18}|", "", ""));
+        lines.extend((0..STACK).rev().map(|r| row(r, &bottom, bar.1)));
+        lines.push(border(&bottom));
+
+        // trays next to the top and bottom rows
+        for (i, line) in tray(player, off.0).iter().enumerate() {
+            lines[1 + i].push_str(line);
+        }
+        for (i, line) in tray(opponent, off.1).iter().enumerate() {
+            lines[2 * STACK - 2 + i].push_str(line);
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+impl Game {
+    /// Draw the game as an ASCII diagram, seen from the player on roll, followed by the cube, the
+    /// dices and the pip counts. See [`Board::to_ascii`] for the diagram of the board.
+    pub fn to_ascii(&self) -> String {
+        let mut s = self.board.to_ascii(self.who_plays);
+
+        s.push_str(&format!("Cube: {}", self.cube.value()));
+        if self.cube.owner() != Player::Nobody {
+            s.push_str(&format!(", owned by {}", checker_char(self.cube.owner())));
+        }
+        s.push('\n');
+
+        if self.result.is_none() && self.who_plays != Player::Nobody {
+            s.push_str(&format!("To play: {}", checker_char(self.who_plays)));
+            if !self.roll_first {
+                s.push_str(&format!(
+                    ", dices {}-{}",
+                    self.dices.values.0, self.dices.values.1
+                ));
+            }
+            s.push('\n');
+        }
+
+        let pips = |player: Player| self.board.pip_count(player).unwrap_or_default();
+        s.push_str(&format!(
+            "Pips: O {}, X {}\n",
+            pips(Player::Player0),
+            pips(Player::Player1)
+        ));
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Roll;
+    use crate::rules::{CheckerMove, Field, ScriptedDices};
+    use crate::Error;
+
+    #[test]
+    fn board_to_ascii() {
+        assert_eq!(
+            Board::new().to_ascii(Player::Player0),
+            "\
++12-11-10--9--8--7-------6--5--4--3--2--1-+
+| X           O    |   | O              X | +-------+
+| X           O    |   | O              X | | OFF O |
+| X           O    |   | O                | |   0   |
+| X                |   | O                | +-------+
+| X                |   | O                |
+|                  |BAR|                  |
+| O                |   | X                |
+| O                |   | X                | +-------+
+| O           X    |   | X                | | OFF X |
+| O           X    |   | X              O | |   0   |
+| O           X    |   | X              O | +-------+
++13-14-15-16-17-18------19-20-21-22-23-24-+
+"
+        );
+    }
+
+    #[test]
+    fn board_to_ascii_player1() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set(Player::Player1, 5, 1)?;
+        board.set_bar(Player::Player1, 1)?;
+        board.set_bar(Player::Player0, 6)?;
+        board.set_off(Player::Player1, 2)?;
+        assert_eq!(
+            board.to_ascii(Player::Player1),
+            "\
++12-11-10--9--8--7-------6--5--4--3--2--1-+
+| O           X    | 6 | X              O | +-------+
+| O           X    | O | X              O | | OFF X |
+| O           X    | O | X                | |   2   |
+| O                | O | X                | +-------+
+| O                | O | 6                |
+|                  |BAR|                  |
+| X                | X | O                |
+| X                |   | O                | +-------+
+| X           O    |   | O                | | OFF O |
+| X           O    |   | O              X | |   0   |
+| X           O    |   | O              X | +-------+
++13-14-15-16-17-18------19-20-21-22-23-24-+
+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn game_to_ascii() -> Result<(), Error> {
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(6, 4)]))?;
+        assert!(game
+            .to_ascii()
+            .ends_with("Cube: 1\nTo play: O, dices 6-4\nPips: O 167, X 167\n"));
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(17), Field::Point(13)),
+            ],
+        )?;
+        assert!(game
+            .to_ascii()
+            .ends_with("Cube: 1\nTo play: X\nPips: O 157, X 167\n"));
+        Ok(())
+    }
+}
@@ -8,10 +8,14 @@ pub enum Error {
     GameStarted,
     /// Game has already ended
     GameEnded,
+    /// Match has already ended
+    MatchEnded,
     /// Opponent offered doubling cube. Need to react on this event first.
     CubeReceived,
     /// Doubling not permitted
     DoublingNotPermitted,
+    /// No cube has been offered
+    CubeNotOffered,
     /// Invalid cube value
     CubeValueInvalid,
     /// Invalid player
@@ -20,18 +24,54 @@ pub enum Error {
     FieldBlocked,
     /// Invalid field
     FieldInvalid,
+    /// Invalid position
+    PositionInvalid,
+    /// Invalid GNU Backgammon position ID
+    PositionIdInvalid,
+    /// Invalid GNU Backgammon match ID
+    MatchIdInvalid,
+    /// Invalid eXtreme Gammon XGID
+    XgidInvalid,
+    /// Invalid FIBS board string
+    FibsBoardInvalid,
+    /// Login to the FIBS server failed
+    FibsLoginFailed,
+    /// Reading from or writing to the FIBS server failed
+    FibsTransport(String),
+    /// Invalid .mat match file
+    MatInvalid(String),
+    /// Invalid SGF game record
+    SgfInvalid(String),
+    /// Action does not match the state of the game
+    ActionInvalid,
     /// Not your turn
     NotYourTurn,
     /// Invalid move
     MoveInvalid,
     /// Invalid move, checker on bar
     MoveInvalidBar,
+    /// Invalid move, bearing off not permitted
+    MoveInvalidOff,
+    /// Invalid move, all dices have to be played if possible
+    MoveInvalidAllDices,
+    /// Invalid move, the larger dice has to be played if only one can be played
+    MoveInvalidLargerDice,
     /// Move first
     MoveFirst,
+    /// Commit the turn first
+    CommitFirst,
+    /// No move to undo
+    NothingToUndo,
+    /// No move to redo
+    NothingToRedo,
     /// Roll first
     RollFirst,
     /// Dice Invalid
     DiceInvalid,
+    /// Invalid move notation, with a description of the problem
+    NotationInvalid(String),
+    /// No more dice rolls available
+    DiceExhausted,
 }
 
 // implement Error trait
@@ -43,6 +83,7 @@ impl fmt::Display for Error {
         match self {
             Error::GameStarted => write!(f, "Game has already started"),
             Error::GameEnded => write!(f, "Game has already ended"),
+            Error::MatchEnded => write!(f, "Match has already ended"),
             Error::PlayerInvalid => write!(f, "Invalid player"),
             Error::CubeReceived => {
                 write!(
@@ -52,14 +93,38 @@ impl fmt::Display for Error {
             }
             Error::CubeValueInvalid => write!(f, "Invalid cube value"),
             Error::DoublingNotPermitted => write!(f, "Doubling not permitted"),
+            Error::CubeNotOffered => write!(f, "No cube has been offered"),
             Error::FieldBlocked => write!(f, "Field blocked"),
             Error::FieldInvalid => write!(f, "Invalid field"),
+            Error::PositionInvalid => write!(f, "Invalid position"),
+            Error::PositionIdInvalid => write!(f, "Invalid position ID"),
+            Error::MatchIdInvalid => write!(f, "Invalid match ID"),
+            Error::XgidInvalid => write!(f, "Invalid XGID"),
+            Error::FibsBoardInvalid => write!(f, "Invalid FIBS board"),
+            Error::FibsLoginFailed => write!(f, "FIBS login failed"),
+            Error::FibsTransport(reason) => write!(f, "FIBS transport failed: {}", reason),
+            Error::MatInvalid(reason) => write!(f, "Invalid match file: {}", reason),
+            Error::SgfInvalid(reason) => write!(f, "Invalid SGF: {}", reason),
+            Error::ActionInvalid => write!(f, "Invalid action"),
             Error::NotYourTurn => write!(f, "Not your turn"),
             Error::MoveInvalid => write!(f, "Invalid move"),
             Error::MoveFirst => write!(f, "Move first"),
+            Error::CommitFirst => write!(f, "Commit the turn first"),
+            Error::NothingToUndo => write!(f, "No move to undo"),
+            Error::NothingToRedo => write!(f, "No move to redo"),
             Error::RollFirst => write!(f, "Roll first"),
             Error::DiceInvalid => write!(f, "Invalid dice"),
+            Error::NotationInvalid(reason) => write!(f, "Invalid notation: {}", reason),
+            Error::DiceExhausted => write!(f, "No more dice rolls available"),
             Error::MoveInvalidBar => write!(f, "Invalid move, checker on bar"),
+            Error::MoveInvalidOff => write!(f, "Invalid move, bearing off not permitted"),
+            Error::MoveInvalidAllDices => {
+                write!(f, "Invalid move, all dices have to be played if possible")
+            }
+            Error::MoveInvalidLargerDice => write!(
+                f,
+                "Invalid move, the larger dice has to be played if only one can be played"
+            ),
         }
     }
 }
@@ -75,6 +140,7 @@ mod tests {
             "Game has already started"
         );
         assert_eq!(format!("{}", Error::GameEnded), "Game has already ended");
+        assert_eq!(format!("{}", Error::MatchEnded), "Match has already ended");
         assert_eq!(format!("{}", Error::PlayerInvalid), "Invalid player");
         assert_eq!(
             format!("{}", Error::CubeReceived),
@@ -85,16 +151,65 @@ mod tests {
             format!("{}", Error::DoublingNotPermitted),
             "Doubling not permitted"
         );
+        assert_eq!(
+            format!("{}", Error::CubeNotOffered),
+            "No cube has been offered"
+        );
         assert_eq!(format!("{}", Error::FieldBlocked), "Field blocked");
         assert_eq!(format!("{}", Error::FieldInvalid), "Invalid field");
+        assert_eq!(format!("{}", Error::PositionInvalid), "Invalid position");
+        assert_eq!(
+            format!("{}", Error::PositionIdInvalid),
+            "Invalid position ID"
+        );
+        assert_eq!(format!("{}", Error::MatchIdInvalid), "Invalid match ID");
+        assert_eq!(format!("{}", Error::XgidInvalid), "Invalid XGID");
+        assert_eq!(format!("{}", Error::FibsBoardInvalid), "Invalid FIBS board");
+        assert_eq!(format!("{}", Error::FibsLoginFailed), "FIBS login failed");
+        assert_eq!(
+            format!("{}", Error::FibsTransport("closed".to_string())),
+            "FIBS transport failed: closed"
+        );
+        assert_eq!(
+            format!("{}", Error::MatInvalid("line 1".to_string())),
+            "Invalid match file: line 1"
+        );
+        assert_eq!(
+            format!("{}", Error::SgfInvalid("GM[1]".to_string())),
+            "Invalid SGF: GM[1]"
+        );
+        assert_eq!(format!("{}", Error::ActionInvalid), "Invalid action");
         assert_eq!(format!("{}", Error::NotYourTurn), "Not your turn");
         assert_eq!(format!("{}", Error::MoveInvalid), "Invalid move");
         assert_eq!(format!("{}", Error::MoveFirst), "Move first");
+        assert_eq!(format!("{}", Error::CommitFirst), "Commit the turn first");
+        assert_eq!(format!("{}", Error::NothingToUndo), "No move to undo");
+        assert_eq!(format!("{}", Error::NothingToRedo), "No move to redo");
         assert_eq!(format!("{}", Error::RollFirst), "Roll first");
         assert_eq!(format!("{}", Error::DiceInvalid), "Invalid dice");
+        assert_eq!(
+            format!("{}", Error::NotationInvalid("13/25".to_string())),
+            "Invalid notation: 13/25"
+        );
+        assert_eq!(
+            format!("{}", Error::DiceExhausted),
+            "No more dice rolls available"
+        );
         assert_eq!(
             format!("{}", Error::MoveInvalidBar),
             "Invalid move, checker on bar"
         );
+        assert_eq!(
+            format!("{}", Error::MoveInvalidOff),
+            "Invalid move, bearing off not permitted"
+        );
+        assert_eq!(
+            format!("{}", Error::MoveInvalidAllDices),
+            "Invalid move, all dices have to be played if possible"
+        );
+        assert_eq!(
+            format!("{}", Error::MoveInvalidLargerDice),
+            "Invalid move, the larger dice has to be played if only one can be played"
+        );
     }
 }
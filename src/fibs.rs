@@ -0,0 +1,360 @@
+/// Implements the FIBS client protocol
+mod client;
+pub use client::{Client, Event, WhoInfo};
+
+use crate::rules::{Board, Dices, Player};
+use crate::{Error, Game};
+
+/// Match length used by FIBS for unlimited matches
+const UNLIMITED: u32 = 9999;
+
+/// Position on the FIBS board of a field of Player0, given the direction Player0 moves in
+fn position(field: usize, direction: i32) -> usize {
+    if direction < 0 {
+        field + 1
+    } else {
+        24 - field
+    }
+}
+
+impl Game {
+    /// Get the FIBS board string of the game, seen from Player0 as `player`, playing against
+    /// Player1 as `opponent`.
+    ///
+    /// Player0 plays the colour O with positive numbers on the board and moves from position 24
+    /// down to its home at position 0.
+    ///
+    /// ```
+    /// use backgammon::Game;
+    ///
+    /// let board = Game::new().to_fibs("alice", "bob");
+    /// assert!(board.starts_with("board:alice:bob:7:0:0:0:-2:0:0:0:0:5:0:3:0:0:0:-5:5:"));
+    /// ```
+    pub fn to_fibs(&self, player: &str, opponent: &str) -> String {
+        let board = self.board.get();
+        let mut points = [0i8; 26];
+        points[1..25].copy_from_slice(&board.board);
+        points[25] = board.bar.0 as i8;
+        points[0] = -(board.bar.1 as i8);
+
+        let turn = match (self.result, self.who_plays) {
+            (Some(_), _) => 0,
+            (None, Player::Player1) => -1,
+            (None, _) => 1,
+        };
+        let rolled = self.result.is_none() && self.who_plays != Player::Nobody && !self.roll_first;
+        let dices = match (rolled, self.who_plays) {
+            (true, Player::Player0) => (self.dices.values, (0, 0)),
+            (true, _) => ((0, 0), self.dices.values),
+            (false, _) => ((0, 0), (0, 0)),
+        };
+        let may_double = |player: Player| {
+            !self.crawford && self.cube.owner() != player.other() && self.result.is_none()
+        };
+        let can_move = match (rolled, self.who_plays) {
+            (true, Player::Player0) => self.legal_plays().map_or(0, |plays| plays[0].len()),
+            _ => 0,
+        };
+
+        let mut fields = vec![
+            String::from("board"),
+            player.to_string(),
+            opponent.to_string(),
+            match self.rules.points {
+                0 => UNLIMITED,
+                points => points,
+            }
+            .to_string(),
+            self.score.0.to_string(),
+            self.score.1.to_string(),
+        ];
+        fields.extend(points.iter().map(|p| p.to_string()));
+        fields.extend(
+            [
+                turn,
+                dices.0 .0 as i32,
+                dices.0 .1 as i32,
+                dices.1 .0 as i32,
+                dices.1 .1 as i32,
+                self.cube.value() as i32,
+                may_double(Player::Player0) as i32,
+                may_double(Player::Player1) as i32,
+                (self.cube_received && self.who_plays == Player::Player1) as i32,
+                // colour and direction of Player0, followed by its home and bar
+                1,
+                -1,
+                0,
+                25,
+                board.off.0 as i32,
+                board.off.1 as i32,
+                board.bar.0 as i32,
+                board.bar.1 as i32,
+                can_move as i32,
+                // forced moves are not supported
+                0,
+                self.post_crawford() as i32,
+                // redoubles are not limited
+                0,
+            ]
+            .iter()
+            .map(|v| v.to_string()),
+        );
+        fields.join(":")
+    }
+
+    /// Create a game from a FIBS board string.
+    ///
+    /// The player the board is seen from becomes Player0 and the opponent becomes Player1,
+    /// whatever colour and direction they play on FIBS. The names of the players are not kept.
+    pub fn from_fibs(board: &str) -> Result<Self, Error> {
+        let fields: Vec<&str> = board.trim().split(':').collect();
+        if fields.len() != 53 || fields[0] != "board" {
+            return Err(Error::FibsBoardInvalid);
+        }
+        let values = fields[3..]
+            .iter()
+            .map(|f| f.parse::<i32>().map_err(|_| Error::FibsBoardInvalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        // values[i] is the field i + 3 of the board string
+        let value = |i: usize| values[i - 3];
+
+        let colour = value(41);
+        let direction = value(42);
+        if colour.abs() != 1 || direction.abs() != 1 {
+            return Err(Error::FibsBoardInvalid);
+        }
+
+        // checkers of the player have the sign of its colour, the bar is opposite of its home
+        let mut player0 = [0u8; 25];
+        let mut player1 = [0u8; 25];
+        for field in 0..24 {
+            let checkers = value(6 + position(field, direction)) * colour;
+            if checkers > 0 {
+                player0[field] = checkers as u8;
+            } else if checkers < 0 {
+                player1[23 - field] = (-checkers) as u8;
+            }
+        }
+        player0[24] = value(47).try_into().map_err(|_| Error::FibsBoardInvalid)?;
+        player1[24] = value(48).try_into().map_err(|_| Error::FibsBoardInvalid)?;
+
+        let mut game = Game::new();
+        game.board =
+            Board::from_checkers(&player0, &player1).map_err(|_| Error::FibsBoardInvalid)?;
+        if game.board.off(Player::Player0)? as i32 != value(45)
+            || game.board.off(Player::Player1)? as i32 != value(46)
+        {
+            return Err(Error::FibsBoardInvalid);
+        }
+
+        let points = value(3);
+        let score = (value(4), value(5));
+        if points < 0 || score.0 < 0 || score.1 < 0 {
+            return Err(Error::FibsBoardInvalid);
+        }
+        game.rules.points = match points as u32 {
+            UNLIMITED => 0,
+            points => points,
+        };
+        game.score = (score.0 as u32, score.1 as u32);
+
+        // cube
+        let cube = value(37);
+        if cube < 1 {
+            return Err(Error::FibsBoardInvalid);
+        }
+        game.cube.set(cube as u64)?;
+        // nobody may double a centered cube in the Crawford game, which is only played while a
+        // player is one point away from winning the match
+        let match_point = points > 0 && (score.0 == points - 1 || score.1 == points - 1);
+        match (value(38), value(39)) {
+            (0, 0) => game.crawford = cube == 1 && match_point && value(32) != 0,
+            (_, 0) => game.cube.set_owner(Player::Player0),
+            (0, _) => game.cube.set_owner(Player::Player1),
+            _ => {}
+        }
+        game.set_crawford(game.crawford, value(51) != 0);
+
+        // turn and dices
+        let turn = value(32);
+        let (player, dices) = if turn == colour {
+            (Player::Player0, (value(33), value(34)))
+        } else if turn == -colour {
+            (Player::Player1, (value(35), value(36)))
+        } else if turn == 0 {
+            for player in [Player::Player0, Player::Player1] {
+                if game.board.off(player)? == 15 {
+                    game.winner = player;
+                    game.result = Some(game.board_result(player)?);
+                }
+            }
+            return Ok(game);
+        } else {
+            return Err(Error::FibsBoardInvalid);
+        };
+
+        game.who_plays = player;
+        if value(40) != 0 {
+            // the opponent has doubled, the player has to respond
+            game.who_plays = Player::Player1;
+            game.roll_first = true;
+            game.cube_received = true;
+        } else if dices == (0, 0) {
+            game.roll_first = true;
+        } else if (1..=6).contains(&dices.0) && (1..=6).contains(&dices.1) {
+            game.dices = Dices::from_values((dices.0 as u8, dices.1 as u8));
+        } else {
+            return Err(Error::FibsBoardInvalid);
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{CheckerMove, Field, Roll, ScriptedDices};
+
+    /// Example of the FIBS client protocol documentation, O to play 62
+    const EXAMPLE: &str = "board:You:someplayer:3:0:0:0:-2:0:0:0:0:5:0:3:0:0:0:-5:5:0:0:0:-3:0:-5:0:0:0:0:2:0:1:6:2:0:0:1:1:1:0:1:-1:0:25:0:0:0:0:2:0:0:0";
+
+    #[test]
+    fn from_fibs_example() -> Result<(), Error> {
+        let game = Game::from_fibs(EXAMPLE)?;
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.rules.points, 3);
+        assert_eq!(game.who_plays, Player::Player0);
+        assert_eq!(game.dices.values, (6, 2));
+        assert_eq!(game.cube.owner(), Player::Nobody);
+        assert!(!game.crawford());
+        assert_eq!(game.legal_plays()?[0].len(), 2);
+        assert_eq!(game.to_fibs("You", "someplayer"), EXAMPLE);
+        Ok(())
+    }
+
+    #[test]
+    fn from_fibs_colour_and_direction() -> Result<(), Error> {
+        // the same position seen by X, moving from position 1 up to home at 25
+        let mut fields: Vec<String> = EXAMPLE.split(':').map(String::from).collect();
+        let points: Vec<String> = fields[6..32].iter().rev().cloned().collect();
+        let _ = fields.splice(6..32, points);
+        fields[32] = String::from("-1");
+        fields[41] = String::from("-1");
+        fields[42] = String::from("1");
+        fields[43] = String::from("25");
+        fields[44] = String::from("0");
+        for point in &mut fields[6..32] {
+            *point = (-point.parse::<i32>().unwrap()).to_string();
+        }
+        let game = Game::from_fibs(&fields.join(":"))?;
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.who_plays, Player::Player0);
+        assert_eq!(game.dices.values, (6, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn to_fibs_round_trip() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 0;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(1, 3), (6, 4)]))?;
+        let _ = game.play(
+            Player::Player1,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.offer_double(Player::Player0)?;
+        let _ = game.accept_double(Player::Player1)?;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(6, 4)]))?;
+
+        let board = game.to_fibs("alice", "bob");
+        assert!(board.starts_with("board:alice:bob:9999:0:0:"));
+        let imported = Game::from_fibs(&board)?;
+        assert_eq!(imported.board, game.board);
+        assert_eq!(imported.who_plays, Player::Player0);
+        assert_eq!(imported.dices.values, (6, 4));
+        assert_eq!(imported.cube.value(), 2);
+        assert_eq!(imported.cube.owner(), Player::Player1);
+        assert!(imported.rules.money_play());
+        assert_eq!(imported.to_fibs("alice", "bob"), board);
+        Ok(())
+    }
+
+    #[test]
+    fn from_fibs_doubled() -> Result<(), Error> {
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(1, 3)]))?;
+        let _ = game.play(
+            Player::Player1,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(2, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(12), Field::Point(10)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.offer_double(Player::Player1)?;
+
+        let mut imported = Game::from_fibs(&game.to_fibs("alice", "bob"))?;
+        let _ = imported.accept_double(Player::Player0)?;
+        assert_eq!(imported.cube.owner(), Player::Player0);
+        Ok(())
+    }
+
+    #[test]
+    fn from_fibs_crawford() -> Result<(), Error> {
+        let replace = |fields: &[(usize, &str)]| {
+            let mut board: Vec<&str> = EXAMPLE.split(':').collect();
+            for &(i, value) in fields {
+                board[i] = value;
+            }
+            board.join(":")
+        };
+        let no_double = [(38, "0"), (39, "0")];
+
+        let game = Game::from_fibs(&replace(&[no_double[0], no_double[1], (5, "2")]))?;
+        assert!(game.crawford());
+
+        // neither player may double, but nobody is at match point
+        let game = Game::from_fibs(&replace(&no_double))?;
+        assert!(!game.crawford());
+        Ok(())
+    }
+
+    #[test]
+    fn from_fibs_invalid() {
+        let replace = |i: usize, value: &str| {
+            let mut fields: Vec<&str> = EXAMPLE.split(':').collect();
+            fields[i] = value;
+            fields.join(":")
+        };
+        for board in [
+            String::new(),
+            EXAMPLE.replace("board:", "bord:"),
+            EXAMPLE[..EXAMPLE.len() - 2].to_string(),
+            replace(7, "x"),
+            replace(7, "-3"),
+            replace(8, "2"),
+            replace(32, "2"),
+            replace(33, "7"),
+            replace(37, "0"),
+            replace(41, "0"),
+            replace(45, "1"),
+        ] {
+            assert!(
+                matches!(Game::from_fibs(&board), Err(Error::FibsBoardInvalid)),
+                "{}",
+                board
+            );
+        }
+    }
+}
@@ -0,0 +1,830 @@
+use super::position;
+use crate::rules::{CheckerMove, Field, Player, Roll, ScriptedDices};
+use crate::{Error, Game, Match};
+
+use std::io::{BufRead, Write};
+
+/// Version of the FIBS client protocol (CLIP) spoken by the client
+const CLIP_VERSION: u32 = 1008;
+
+/// Represents the information about a user logged in to the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhoInfo {
+    /// name of the user
+    pub name: String,
+    /// name of the opponent if the user is playing
+    pub opponent: Option<String>,
+    /// name of the player the user is watching
+    pub watching: Option<String>,
+    /// true if the user is ready to play
+    pub ready: bool,
+    /// true if the user is away
+    pub away: bool,
+    /// rating of the user
+    pub rating: f64,
+    /// experience of the user, the amount of points played
+    pub experience: u32,
+}
+
+/// Represents an event sent by the FIBS server
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Login succeeded
+    Welcome {
+        /// name of the user logged in
+        name: String,
+    },
+    /// Settings of the user logged in, as sent by the server
+    OwnInfo(String),
+    /// Start of the message of the day
+    MotdStart,
+    /// End of the message of the day
+    MotdEnd,
+    /// Information about a user, sent after login and whenever it changes
+    Who(WhoInfo),
+    /// End of a list of users
+    WhoEnd,
+    /// A user logged in
+    Login {
+        /// name of the user
+        name: String,
+    },
+    /// A user logged out
+    Logout {
+        /// name of the user
+        name: String,
+    },
+    /// A message from a user
+    Message {
+        /// name of the sender
+        from: String,
+        /// the message
+        message: String,
+    },
+    /// A user said something to you, or shouted, whispered or kibitzed
+    Chat {
+        /// name of the user
+        name: String,
+        /// the message
+        message: String,
+    },
+    /// A user invites you to play a match, with the length of the match or none to resume a
+    /// saved match or play an unlimited match
+    Invitation {
+        /// name of the user
+        name: String,
+        /// length of the match
+        points: Option<u32>,
+    },
+    /// The board has been updated, see [`Client::game`]
+    Board,
+    /// A player rolled the dices
+    Rolls {
+        /// name of the player, `You` for yourself
+        name: String,
+        /// values of the two dices
+        dices: (u8, u8),
+    },
+    /// A player moved checkers, seen from the player who moved
+    Moves {
+        /// name of the player, `You` for yourself
+        name: String,
+        /// the checker moves
+        moves: Vec<CheckerMove>,
+    },
+    /// A player doubled
+    Doubles {
+        /// name of the player, `You` for yourself
+        name: String,
+    },
+    /// A player accepted the double
+    Accepts {
+        /// name of the player, `You` for yourself
+        name: String,
+    },
+    /// A player rejected the double
+    Rejects {
+        /// name of the player, `You` for yourself
+        name: String,
+    },
+    /// Any other line sent by the server
+    Text(String),
+}
+
+/// Implements a client for the FIBS server.
+///
+/// The client speaks the FIBS client protocol (CLIP) over any reader and writer, e.g. the two
+/// halves of a TCP stream, and keeps track of the match played. Board updates of the server set
+/// the game, and the rolls, moves and cube actions in between are played on it. Board updates
+/// have to be sent in the FIBS board format, see `set boardstyle 3`.
+///
+/// ```
+/// use backgammon::fibs::{Client, Event};
+/// use std::io::Cursor;
+///
+/// let server = "login: 1 alice 1041253132 192.168.1.308\r\n";
+/// let mut client = Client::new(Cursor::new(server), Vec::new());
+/// client.login("backgammon", "alice", "secret")?;
+/// client.roll()?;
+///
+/// let (_, sent) = client.into_inner();
+/// assert_eq!(sent, b"login backgammon 1008 alice secret\r\nroll\r\n");
+/// # Ok::<(), backgammon::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Client<R, W> {
+    /// reads from the server
+    reader: R,
+    /// writes to the server
+    writer: W,
+    /// name of the user logged in
+    user: String,
+    /// direction the user moves in on the FIBS board, -1 for moving to position 0
+    direction: i32,
+    /// match shown on the board, seen from the user as Player0
+    game_match: Option<Match>,
+}
+
+impl<R: BufRead, W: Write> Client<R, W> {
+    /// Create a client reading from and writing to the server
+    pub fn new(reader: R, writer: W) -> Self {
+        Client {
+            reader,
+            writer,
+            user: String::new(),
+            direction: -1,
+            game_match: None,
+        }
+    }
+
+    /// Returns the reader and the writer of the client
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    /// Returns the name of the user logged in
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the names of the user and the opponent of the current game
+    pub fn players(&self) -> (&str, &str) {
+        match &self.game_match {
+            Some(m) => (&m.players.0, &m.players.1),
+            None => ("", ""),
+        }
+    }
+
+    /// Returns the match played, seen from the user as Player0. It holds the length of the match,
+    /// the names of the players and a game for each score shown on the board.
+    pub fn game_match(&self) -> Option<&Match> {
+        self.game_match.as_ref()
+    }
+
+    /// Returns the current game, seen from the user as Player0
+    pub fn game(&self) -> Option<&Game> {
+        self.game_match.as_ref().and_then(|m| m.games.last())
+    }
+
+    /// Log in to the server. Returns once the server welcomes the user, or an error if the server
+    /// asks for the login again or closes the connection.
+    pub fn login(&mut self, client: &str, user: &str, password: &str) -> Result<(), Error> {
+        self.send(&format!(
+            "login {} {} {} {}",
+            client, CLIP_VERSION, user, password
+        ))?;
+
+        // the server prompts once before reading the login, and again if the login failed
+        let mut prompted = false;
+        while let Some(line) = self.read_line()? {
+            let line = match line.strip_prefix("login: ") {
+                Some("") if prompted => return Err(Error::FibsLoginFailed),
+                Some(line) => {
+                    prompted = true;
+                    line.to_string()
+                }
+                None => line,
+            };
+            if let Event::Welcome { name } = self.parse(&line) {
+                self.user = name;
+                return Ok(());
+            }
+        }
+        Err(Error::FibsLoginFailed)
+    }
+
+    /// Send a command to the server
+    pub fn send(&mut self, command: &str) -> Result<(), Error> {
+        let transport = |e: std::io::Error| Error::FibsTransport(e.to_string());
+        self.writer
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .map_err(transport)?;
+        self.writer.flush().map_err(transport)
+    }
+
+    /// Invite a user to play a match of the given length, or an unlimited match
+    pub fn invite(&mut self, name: &str, points: Option<u32>) -> Result<(), Error> {
+        match points {
+            Some(points) => self.send(&format!("invite {} {}", name, points)),
+            None => self.send(&format!("invite {} unlimited", name)),
+        }
+    }
+
+    /// Accept the invitation of a user
+    pub fn join(&mut self, name: &str) -> Result<(), Error> {
+        self.send(&format!("join {}", name))
+    }
+
+    /// Roll the dices
+    pub fn roll(&mut self) -> Result<(), Error> {
+        self.send("roll")
+    }
+
+    /// Offer the cube to the opponent
+    pub fn double(&mut self) -> Result<(), Error> {
+        self.send("double")
+    }
+
+    /// Accept the double of the opponent
+    pub fn accept(&mut self) -> Result<(), Error> {
+        self.send("accept")
+    }
+
+    /// Reject the double of the opponent
+    pub fn reject(&mut self) -> Result<(), Error> {
+        self.send("reject")
+    }
+
+    /// Move checkers of the user, seen from the user
+    pub fn play(&mut self, moves: &[CheckerMove]) -> Result<(), Error> {
+        let mut command = String::from("move");
+        for m in moves {
+            command.push_str(&format!(
+                " {}-{}",
+                self.fibs_field(m.from),
+                self.fibs_field(m.to)
+            ));
+        }
+        self.send(&command)
+    }
+
+    /// Read the next event from the server and update the game, or none if the server closed the
+    /// connection.
+    ///
+    /// Rolls, moves and cube actions are played on the current game. Returns an error if the game
+    /// does not permit them; the game is left unchanged in that case.
+    pub fn next_event(&mut self) -> Result<Option<Event>, Error> {
+        let Some(line) = self.read_line()? else {
+            return Ok(None);
+        };
+
+        if line.starts_with("board:") {
+            let game = Game::from_fibs(&line)?;
+            let fields: Vec<&str> = line.split(':').collect();
+            let players = (fields[1].to_string(), fields[2].to_string());
+            self.direction = fields[42].parse().map_err(|_| Error::FibsBoardInvalid)?;
+
+            // a new match starts if the opponent or the length changes
+            let mut m = match self.game_match.take() {
+                Some(m) if m.players == players && m.rules.points == game.rules.points => m,
+                _ => Match {
+                    rules: game.rules,
+                    games: Vec::new(),
+                    players,
+                },
+            };
+            // a new game starts if the score changes
+            match m.games.last_mut() {
+                Some(last) if last.score == game.score => *last = game,
+                _ => m.games.push(game),
+            }
+            self.game_match = Some(m);
+            return Ok(Some(Event::Board));
+        }
+
+        let event = self.parse(&line);
+        self.apply(&event)?;
+        Ok(Some(event))
+    }
+
+    /// Play an event on the current game
+    fn apply(&mut self, event: &Event) -> Result<(), Error> {
+        let Some(game) = self.game_match.as_ref().and_then(|m| m.games.last()) else {
+            return Ok(());
+        };
+        let mut game = game.clone();
+        match event {
+            Event::Rolls { name, dices } => {
+                let player = self.player(name);
+                let _ = game.roll_with(&mut ScriptedDices::new(vec![*dices]))?;
+                if game.who_plays != player {
+                    return Err(Error::NotYourTurn);
+                }
+            }
+            Event::Moves { name, moves } => {
+                let _ = game.play(self.player(name), moves)?;
+            }
+            Event::Doubles { name } => {
+                let _ = game.offer_double(self.player(name))?;
+            }
+            Event::Accepts { name } => {
+                let _ = game.accept_double(self.player(name))?;
+            }
+            Event::Rejects { name } => {
+                let _ = game.reject_double(self.player(name))?;
+            }
+            _ => return Ok(()),
+        }
+        if let Some(last) = self.game_match.as_mut().and_then(|m| m.games.last_mut()) {
+            *last = game;
+        }
+        Ok(())
+    }
+
+    /// Player of a name, Player0 for the user and Player1 for the opponent
+    fn player(&self, name: &str) -> Player {
+        if self.is_user(name) {
+            Player::Player0
+        } else {
+            Player::Player1
+        }
+    }
+
+    /// Check if a name refers to the user
+    fn is_user(&self, name: &str) -> bool {
+        name == "You" || name == self.user || name == self.players().0
+    }
+
+    /// Read a line from the server, without the line ending. The login prompt is returned as a
+    /// line of its own, as it is not terminated.
+    fn read_line(&mut self) -> Result<Option<String>, Error> {
+        let transport = |e: std::io::Error| Error::FibsTransport(e.to_string());
+        let mut line = Vec::new();
+        loop {
+            let buffer = self.reader.fill_buf().map_err(transport)?;
+            if buffer.is_empty() {
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                break;
+            }
+            match buffer.iter().position(|&b| b == b'\n') {
+                Some(i) => {
+                    line.extend_from_slice(&buffer[..i]);
+                    self.reader.consume(i + 1);
+                    break;
+                }
+                None => {
+                    let n = buffer.len();
+                    line.extend_from_slice(buffer);
+                    self.reader.consume(n);
+                }
+            }
+            if line.ends_with(b"login: ") {
+                break;
+            }
+        }
+
+        let line = String::from_utf8_lossy(&line);
+        Ok(Some(line.trim_end_matches('\r').to_string()))
+    }
+
+    /// Field of the FIBS board, seen from the user
+    fn fibs_field(&self, field: Field) -> String {
+        match field {
+            Field::Bar => String::from("bar"),
+            Field::Off => String::from("off"),
+            Field::Point(n) => position(n, self.direction).to_string(),
+        }
+    }
+
+    /// Field of a player, seen from the player, for a field of the FIBS board
+    fn field(&self, name: &str, field: &str) -> Option<Field> {
+        match field {
+            "bar" => Some(Field::Bar),
+            "off" => Some(Field::Off),
+            _ => {
+                let p: usize = field.parse().ok().filter(|p| (1..=24).contains(p))?;
+                // the opponent moves in the opposite direction
+                let direction = if self.is_user(name) {
+                    self.direction
+                } else {
+                    -self.direction
+                };
+                Some(Field::Point(if direction < 0 { p - 1 } else { 24 - p }))
+            }
+        }
+    }
+
+    /// Parse a line sent by the server
+    fn parse(&self, line: &str) -> Event {
+        let text = || Event::Text(line.to_string());
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let rest = |n: usize| -> String {
+            line.split_whitespace()
+                .skip(n)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let Some(&first) = words.first() else {
+            return text();
+        };
+
+        // CLIP messages start with their number
+        if let Ok(clip) = first.parse::<u32>() {
+            let name = || words.get(1).map(|n| n.to_string()).unwrap_or_default();
+            return match clip {
+                1 if words.len() >= 2 => Event::Welcome { name: name() },
+                2 => Event::OwnInfo(rest(1)),
+                3 => Event::MotdStart,
+                4 => Event::MotdEnd,
+                5 => parse_who(&words).map_or_else(text, Event::Who),
+                6 => Event::WhoEnd,
+                7 => Event::Login { name: name() },
+                8 => Event::Logout { name: name() },
+                9 if words.len() >= 3 => Event::Message {
+                    from: name(),
+                    message: rest(3),
+                },
+                12..=15 => Event::Chat {
+                    name: name(),
+                    message: rest(2),
+                },
+                _ => text(),
+            };
+        }
+
+        let name = first.to_string();
+        match words.get(1..).unwrap_or_default() {
+            ["wants", "to", "play", "a", points, "point", "match", ..] => match points.parse() {
+                Ok(points) => Event::Invitation {
+                    name,
+                    points: Some(points),
+                },
+                Err(_) => text(),
+            },
+            ["wants", "to", "play", "an", "unlimited", "match", ..]
+            | ["wants", "to", "resume", ..] => Event::Invitation { name, points: None },
+            ["rolls" | "roll", d0, "and", d1] => {
+                match (d0.parse(), d1.trim_end_matches('.').parse()) {
+                    (Ok(d0), Ok(d1)) if (1..=6).contains(&d0) && (1..=6).contains(&d1) => {
+                        Event::Rolls {
+                            name,
+                            dices: (d0, d1),
+                        }
+                    }
+                    _ => text(),
+                }
+            }
+            ["moves" | "move", moves @ ..] => {
+                let moves = moves
+                    .iter()
+                    .filter(|m| **m != ".")
+                    .map(|m| {
+                        let (from, to) = m.trim_end_matches('.').split_once('-')?;
+                        Some(CheckerMove::new(
+                            self.field(&name, from)?,
+                            self.field(&name, to)?,
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match moves {
+                    Some(moves) => Event::Moves { name, moves },
+                    None => text(),
+                }
+            }
+            ["doubles." | "double.", ..] => Event::Doubles { name },
+            ["accepts" | "accept", "the", "double.", ..] => Event::Accepts { name },
+            ["rejects" | "reject" | "refuses" | "refuse", "the", "double.", ..] => {
+                Event::Rejects { name }
+            }
+            _ => text(),
+        }
+    }
+}
+
+/// Parse the information about a user: `5 name opponent watching ready away rating experience
+/// idle login hostname client email`
+fn parse_who(words: &[&str]) -> Option<WhoInfo> {
+    let optional = |name: &str| match name {
+        "-" => None,
+        name => Some(name.to_string()),
+    };
+    Some(WhoInfo {
+        name: words.get(1)?.to_string(),
+        opponent: optional(words.get(2)?),
+        watching: optional(words.get(3)?),
+        ready: *words.get(4)? == "1",
+        away: *words.get(5)? == "1",
+        rating: words.get(6)?.parse().ok()?,
+        experience: words.get(7)?.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Board, Player};
+    use std::io::Cursor;
+
+    const BOARD: &str = "board:You:someplayer:3:0:0:0:-2:0:0:0:0:5:0:3:0:0:0:-5:5:0:0:0:-3:0:-5:0:0:0:0:2:0:1:6:2:0:0:1:1:1:0:1:-1:0:25:0:0:0:0:2:0:0:0";
+
+    /// Create a client reading the script of a stand-in server
+    fn client(script: &str) -> Client<Cursor<Vec<u8>>, Vec<u8>> {
+        Client::new(Cursor::new(script.as_bytes().to_vec()), Vec::new())
+    }
+
+    /// Returns all commands sent by the client
+    fn sent(client: Client<Cursor<Vec<u8>>, Vec<u8>>) -> String {
+        String::from_utf8(client.into_inner().1).unwrap()
+    }
+
+    #[test]
+    fn login() -> Result<(), Error> {
+        let mut c = client(
+            "\r\nFIBS banner\r\nlogin: 1 alice 1041253132 192.168.1.308\r\n\
+             2 alice 1 1 0 0 0 0 1 1 2396 0 1 0 1 3457.85 0 0 0 0 0 Australia/Melbourne\r\n",
+        );
+        c.login("backgammon", "alice", "secret")?;
+        assert_eq!(c.user(), "alice");
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::OwnInfo(String::from(
+                "alice 1 1 0 0 0 0 1 1 2396 0 1 0 1 3457.85 0 0 0 0 0 Australia/Melbourne"
+            )))
+        );
+        assert_eq!(c.next_event()?, None);
+        assert_eq!(sent(c), "login backgammon 1008 alice secret\r\n");
+        Ok(())
+    }
+
+    #[test]
+    fn login_failed() {
+        let mut c = client("FIBS banner\r\nlogin: ");
+        assert!(matches!(
+            c.login("backgammon", "alice", "wrong"),
+            Err(Error::FibsLoginFailed)
+        ));
+
+        let mut c = client("login: \r\nlogin: ");
+        assert!(matches!(
+            c.login("backgammon", "alice", "wrong"),
+            Err(Error::FibsLoginFailed)
+        ));
+    }
+
+    #[test]
+    fn clip_messages() -> Result<(), Error> {
+        let mut c = client(
+            "3\r\nWelcome to FIBS\r\n4\r\n\
+             5 bob - - 1 0 1550.23 120 5 1041253132 host client email\r\n\
+             5 carol dave - 0 1 1601.00 3000 0 1041253132 host client email\r\n6\r\n\
+             7 bob bob logs in.\r\n8 bob bob drops connection.\r\n\
+             9 bob 1041253132 hello there\r\n12 bob good luck\r\n",
+        );
+        let events: Vec<Event> = std::iter::from_fn(|| c.next_event().unwrap()).collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::MotdStart,
+                Event::Text(String::from("Welcome to FIBS")),
+                Event::MotdEnd,
+                Event::Who(WhoInfo {
+                    name: String::from("bob"),
+                    opponent: None,
+                    watching: None,
+                    ready: true,
+                    away: false,
+                    rating: 1550.23,
+                    experience: 120,
+                }),
+                Event::Who(WhoInfo {
+                    name: String::from("carol"),
+                    opponent: Some(String::from("dave")),
+                    watching: None,
+                    ready: false,
+                    away: true,
+                    rating: 1601.0,
+                    experience: 3000,
+                }),
+                Event::WhoEnd,
+                Event::Login {
+                    name: String::from("bob")
+                },
+                Event::Logout {
+                    name: String::from("bob")
+                },
+                Event::Message {
+                    from: String::from("bob"),
+                    message: String::from("hello there")
+                },
+                Event::Chat {
+                    name: String::from("bob"),
+                    message: String::from("good luck")
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invitations() -> Result<(), Error> {
+        let mut c = client(
+            "bob wants to play a 5 point match with you.\r\n\
+             carol wants to resume a saved match with you.\r\n\
+             dave wants to play an unlimited match with you.\r\n",
+        );
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Invitation {
+                name: String::from("bob"),
+                points: Some(5)
+            })
+        );
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Invitation {
+                name: String::from("carol"),
+                points: None
+            })
+        );
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Invitation {
+                name: String::from("dave"),
+                points: None
+            })
+        );
+        c.join("bob")?;
+        c.invite("carol", Some(3))?;
+        c.invite("dave", None)?;
+        assert_eq!(
+            sent(c),
+            "join bob\r\ninvite carol 3\r\ninvite dave unlimited\r\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn game_events() -> Result<(), Error> {
+        let mut c = client(&format!(
+            "1 alice 1041253132 host\r\n{}\r\n\
+             You move 24-18 13-11 .\r\n\
+             someplayer doubles. Type 'accept' or 'reject'.\r\n\
+             You accept the double. The cube shows 2.\r\n\
+             someplayer rolls 5 and 2.\r\n\
+             someplayer moves 12-17 12-14 .\r\n\
+             You roll 3 and 1.\r\n",
+            BOARD
+        ));
+        c.login("backgammon", "alice", "secret")?;
+
+        assert_eq!(c.next_event()?, Some(Event::Board));
+        let game = c.game().unwrap();
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.who_plays, Player::Player0);
+        assert_eq!(game.dices.values, (6, 2));
+        assert_eq!(c.players(), ("You", "someplayer"));
+        let m = c.game_match().unwrap();
+        assert_eq!(m.rules.points, 3);
+        assert_eq!(m.games.len(), 1);
+
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Moves {
+                name: String::from("You"),
+                moves: vec![
+                    CheckerMove::new(Field::Point(23), Field::Point(17)),
+                    CheckerMove::new(Field::Point(12), Field::Point(10)),
+                ]
+            })
+        );
+        let game = c.game().unwrap();
+        assert_eq!(game.board.get().board[17], 1);
+        assert_eq!(game.board.get().board[10], 1);
+        assert_eq!(game.who_plays, Player::Player1);
+
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Doubles {
+                name: String::from("someplayer")
+            })
+        );
+        assert!(c.game().unwrap().cube_received);
+
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Accepts {
+                name: String::from("You")
+            })
+        );
+        let game = c.game().unwrap();
+        assert_eq!(game.cube.value(), 2);
+        assert_eq!(game.cube.owner(), Player::Player0);
+
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Rolls {
+                name: String::from("someplayer"),
+                dices: (5, 2)
+            })
+        );
+        assert_eq!(c.game().unwrap().dices.values, (5, 2));
+
+        // the opponent moves from position 1 up to its home at 25
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Moves {
+                name: String::from("someplayer"),
+                moves: vec![
+                    CheckerMove::new(Field::Point(12), Field::Point(7)),
+                    CheckerMove::new(Field::Point(12), Field::Point(10)),
+                ]
+            })
+        );
+        let game = c.game().unwrap();
+        assert_eq!(game.board.checkers(Player::Player1)?[12], 3);
+        assert_eq!(game.who_plays, Player::Player0);
+
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Rolls {
+                name: String::from("You"),
+                dices: (3, 1)
+            })
+        );
+        assert_eq!(c.game().unwrap().dices.values, (3, 1));
+        assert_eq!(c.next_event()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn game_events_reject() -> Result<(), Error> {
+        let next_game = BOARD.replace("board:You:someplayer:3:0:0:", "board:You:someplayer:3:0:1:");
+        let mut c = client(&format!(
+            "{}\r\nYou move 24-18 13-11 .\r\nsomeplayer doubles.\r\n\
+             You refuse the double.\r\n{}\r\n",
+            BOARD, next_game
+        ));
+        for _i in 0..3 {
+            let _ = c.next_event()?;
+        }
+        assert_eq!(
+            c.next_event()?,
+            Some(Event::Rejects {
+                name: String::from("You")
+            })
+        );
+        let game = c.game().unwrap();
+        assert_eq!(game.winner, Player::Player1);
+        assert_eq!(game.points(), 1);
+
+        // the next game of the match starts with the new score
+        assert_eq!(c.next_event()?, Some(Event::Board));
+        let m = c.game_match().unwrap();
+        assert_eq!(m.games.len(), 2);
+        assert_eq!(m.games[1].score, (0, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn game_events_invalid() -> Result<(), Error> {
+        let mut c = client(&format!("{}\r\nsomeplayer rolls 5 and 2.\r\n", BOARD));
+        assert_eq!(c.next_event()?, Some(Event::Board));
+        // the user has to move first
+        assert!(matches!(c.next_event(), Err(Error::MoveFirst)));
+        assert_eq!(c.game().unwrap().dices.values, (6, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn commands() -> Result<(), Error> {
+        let mut c = client(&format!("{}\r\n", BOARD));
+        assert_eq!(c.next_event()?, Some(Event::Board));
+        c.roll()?;
+        c.play(&[
+            CheckerMove::new(Field::Point(23), Field::Point(17)),
+            CheckerMove::new(Field::Point(12), Field::Point(10)),
+        ])?;
+        c.play(&[
+            CheckerMove::new(Field::Bar, Field::Point(20)),
+            CheckerMove::new(Field::Point(3), Field::Off),
+        ])?;
+        c.double()?;
+        c.accept()?;
+        c.reject()?;
+        c.send("tell bob thanks")?;
+        assert_eq!(
+            sent(c),
+            "roll\r\nmove 24-18 13-11\r\nmove bar-21 4-off\r\ndouble\r\naccept\r\nreject\r\n\
+             tell bob thanks\r\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_board() {
+        let mut c = client("board:You:someplayer:3\r\n");
+        assert!(matches!(c.next_event(), Err(Error::FibsBoardInvalid)));
+    }
+}
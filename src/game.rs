@@ -1,15 +1,133 @@
 //! # Play a Backgammon Game
 use crate::rules::Cube;
 use crate::rules::Player;
-use crate::rules::{Board, Move};
-use crate::rules::{Dices, Roll};
+use crate::rules::{Board, CheckerMove, Field, Move};
+use crate::rules::{DiceSource, Dices, Roll, ScriptedDices};
 use crate::rules::{GameRules, Rules};
 use crate::Error;
 
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt;
 
+/// Represents how a game has been won
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum GameResult {
+    /// The loser has borne off at least one checker, worth the value of the cube
+    Single,
+    /// The loser has not borne off any checker, worth twice the value of the cube
+    Gammon,
+    /// The loser has not borne off any checker and still has checkers on the bar or in the
+    /// winner's home board, worth three times the value of the cube
+    Backgammon,
+}
+
+impl GameResult {
+    /// Returns the multiplier applied to the value of the cube
+    pub fn multiplier(&self) -> u64 {
+        match self {
+            GameResult::Single => 1,
+            GameResult::Gammon => 2,
+            GameResult::Backgammon => 3,
+        }
+    }
+}
+
+// implement Display trait
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameResult::Single => write!(f, "Single"),
+            GameResult::Gammon => write!(f, "Gammon"),
+            GameResult::Backgammon => write!(f, "Backgammon"),
+        }
+    }
+}
+
+/// Represents an action taken in a game, as recorded in the history of the game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// The dices have been rolled for the player who plays them, or for nobody after a tied
+    /// opening roll
+    Roll {
+        /// player who plays the dices
+        player: Player,
+        /// values of the two dices
+        dices: (u8, u8),
+    },
+    /// A checker has been moved
+    Move {
+        /// player who moved the checker
+        player: Player,
+        /// the checker move
+        checker_move: CheckerMove,
+        /// value of the dice used for the move
+        dice: u8,
+        /// true if a checker of the opponent has been hit
+        hit: bool,
+    },
+    /// The turn has been committed
+    Commit {
+        /// player who committed the turn
+        player: Player,
+    },
+    /// The cube has been offered to the opponent
+    Double {
+        /// player who offered the cube
+        player: Player,
+        /// value of the cube offered
+        value: u64,
+    },
+    /// The offered cube has been accepted
+    Take {
+        /// player who accepted the cube
+        player: Player,
+    },
+    /// The offered cube has been rejected
+    Drop {
+        /// player who rejected the cube
+        player: Player,
+    },
+    /// The offered cube has been beavered
+    Beaver {
+        /// player who beavered
+        player: Player,
+        /// value of the cube after the beaver
+        value: u64,
+    },
+    /// The beaver has been raccooned
+    Raccoon {
+        /// player who raccooned
+        player: Player,
+        /// value of the cube after the raccoon
+        value: u64,
+    },
+    /// A player has resigned
+    Resign {
+        /// player who resigned
+        player: Player,
+        /// result conceded to the opponent
+        result: GameResult,
+    },
+    /// The cube has been doubled automatically after a tied opening roll (Murphy rule)
+    MurphyDouble {
+        /// value of the cube after the automatic double
+        value: u64,
+    },
+    /// The game has ended
+    GameEnd {
+        /// winner of the game
+        winner: Player,
+        /// how the game has been won
+        result: GameResult,
+    },
+}
+
 /// Represents a Backgammon game
+///
+/// Undone moves which can still be redone are neither serialized nor compared, as they are not
+/// part of the state of the game. Fields added after version 0.6.0 default when missing, so
+/// that stored games remain readable.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Game {
     /// rules of the game
@@ -22,14 +140,85 @@ pub struct Game {
     pub board: Board,
     /// cube value and owner
     pub cube: Cube,
+    /// amount of automatic doubles applied due to tied opening rolls (Murphy rule)
+    #[serde(default)]
+    pub murphy_doubles: u8,
+    /// winner of the game, nobody as long as the game is running
+    #[serde(default)]
+    pub winner: Player,
+    /// how the game has been won, none as long as the game is running
+    #[serde(default)]
+    pub result: Option<GameResult>,
+    /// score of the match for both players before this game
+    #[serde(default)]
+    pub score: (u32, u32),
     /// Crawford rule: if crawford game, no doubling allowed
-    crawford: bool,
-    /// Holland rule: if <4 rolls of crawford game, no doubling allowed
+    pub(crate) crawford: bool,
+    /// true if the Crawford game has already been played in the match
+    #[serde(default)]
+    post_crawford: bool,
+    /// Holland rule: if <4 rolls since start of a post-crawford game, no doubling allowed
     since_crawford: u8,
     /// true if player needs to roll first
-    roll_first: bool,
+    pub(crate) roll_first: bool,
     /// if cube was offered, player has to accept first and only then can move on
-    cube_received: bool,
+    pub(crate) cube_received: bool,
+    /// if the double was beavered, the doubler may raccoon before rolling
+    #[serde(default)]
+    pub(crate) beaver_received: bool,
+    /// board and dices before each checker move of the current turn and the recorded move, to undo
+    /// moves
+    #[serde(default)]
+    undo_stack: Vec<(Board, Dices, Action)>,
+    /// board and dices of undone checker moves of the current turn and the recorded moves, to redo
+    /// them
+    #[serde(skip)]
+    redo_stack: Vec<(Board, Dices, Action)>,
+    /// all actions taken in the game so far
+    #[serde(default)]
+    history: Vec<Action>,
+}
+
+impl PartialEq for Game {
+    fn eq(&self, other: &Self) -> bool {
+        let Game {
+            rules,
+            dices,
+            who_plays,
+            board,
+            cube,
+            murphy_doubles,
+            winner,
+            result,
+            score,
+            crawford,
+            post_crawford,
+            since_crawford,
+            roll_first,
+            cube_received,
+            beaver_received,
+            undo_stack,
+            redo_stack: _,
+            history,
+        } = self;
+        *rules == other.rules
+            && *dices == other.dices
+            && *who_plays == other.who_plays
+            && *board == other.board
+            && *cube == other.cube
+            && *murphy_doubles == other.murphy_doubles
+            && *winner == other.winner
+            && *result == other.result
+            && *score == other.score
+            && *crawford == other.crawford
+            && *post_crawford == other.post_crawford
+            && *since_crawford == other.since_crawford
+            && *roll_first == other.roll_first
+            && *cube_received == other.cube_received
+            && *beaver_received == other.beaver_received
+            && *undo_stack == other.undo_stack
+            && *history == other.history
+    }
 }
 
 // implement Display trait
@@ -37,11 +226,7 @@ impl fmt::Display for Game {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut s = String::new();
         s.push_str(&format!("Rules: {}\n", self.rules));
-        s.push_str(&format!("Dices: {:?}\n", self.dices));
-        s.push_str(&format!("Cube: {}\n", self.cube.value()));
-        s.push_str(&format!("Cube owner: {}\n", self.cube.owner()));
-        s.push_str(&format!("Who plays: {}\n", self.who_plays));
-        s.push_str(&format!("Board: {:?}\n", self.board.get()));
+        s.push_str(&self.to_ascii());
         s.push_str(&format!("Crawford game: {}\n", self.crawford));
         s.push_str(&format!("Since Crawford game: {}\n", self.since_crawford));
         write!(f, "{}", s)
@@ -53,139 +238,595 @@ impl Game {
     pub fn new() -> Self {
         Game::default()
     }
-}
 
-impl Roll for Game {
-    fn roll(&mut self) -> Result<&mut Self, Error> {
-        if !self.dices.consumed.0
-            && !self.dices.consumed.1
-            && !self.dices.consumed.2
-            && !self.dices.consumed.3
-        {
-            return Err(Error::MoveFirst);
+    /// Returns true if this is the Crawford game of a match, where doubling is not allowed
+    pub fn crawford(&self) -> bool {
+        self.crawford
+    }
+
+    /// Returns true if this game is played after the Crawford game of a match
+    pub fn post_crawford(&self) -> bool {
+        self.post_crawford
+    }
+
+    /// Returns all actions taken in the game so far, in order. Undone checker moves are removed
+    /// from the history.
+    pub fn history(&self) -> &[Action] {
+        &self.history
+    }
+
+    /// Mark the game as Crawford game or as game after the Crawford game
+    pub(crate) fn set_crawford(&mut self, crawford: bool, post_crawford: bool) {
+        self.crawford = crawford;
+        self.post_crawford = post_crawford;
+    }
+
+    /// List all legal plays for the player whose turn it is, given the dices which have not been
+    /// consumed yet.
+    ///
+    /// Each play is the sequence of checker moves for the rest of the turn, see
+    /// [`Board::legal_plays`]. Different orderings of the same play are listed only once.
+    pub fn legal_plays(&self) -> Result<Vec<Vec<CheckerMove>>, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if self.who_plays == Player::Nobody || self.roll_first {
+            return Err(Error::RollFirst);
         }
         if self.cube_received {
             return Err(Error::CubeReceived);
         }
 
-        self.dices = self.dices.roll();
-        if self.who_plays == Player::Nobody {
-            let diff = self.dices.values.0 - self.dices.values.1;
-            match diff {
-                0 => {
-                    self.who_plays = Player::Nobody;
-                }
-                _ if diff > 0 => {
-                    self.who_plays = Player::Player0;
-                }
-                _ => {
-                    self.who_plays = Player::Player1;
-                }
-            }
-        }
-        Ok(self)
+        self.board
+            .legal_plays(self.who_plays, &self.dices.remaining())
     }
-}
 
-impl Move for Game {
-    fn move_checker(&mut self, player: Player, dice: u8, from: usize) -> Result<&mut Self, Error> {
+    /// Move a checker from a field or from the bar with the given dice, enforcing that the
+    /// maximum amount of dices is used and that the larger dice is played if only one can be.
+    fn play_step(&mut self, player: Player, dice: u8, from: Field) -> Result<&mut Self, Error> {
         // check if move is permitted
         let _ = self.move_permitted(player, dice)?;
 
-        // check if player has to move checker from bar first
-        if ((player == Player::Player0) && (self.board.get().bar.0 > 0))
-            || ((player == Player::Player1) && (self.board.get().bar.1 > 0))
-        {
-            return Err(Error::MoveInvalidBar);
-        }
+        // check if the move itself is possible on the board
+        let m = self.board.step(player, from, dice)?;
 
         // check if the dice value has been consumed
-        if (dice == self.dices.values.0 && self.dices.consumed.0)
-            || (dice == self.dices.values.1 && self.dices.consumed.1)
-            || (dice == self.dices.values.1 && self.dices.consumed.2)
-            || (dice == self.dices.values.1 && self.dices.consumed.3)
+        let remaining = self.dices.remaining();
+        if !remaining.contains(&dice) {
+            return Err(Error::MoveInvalid);
+        }
+
+        // check if the move permits to play as many dices as possible
+        let max = self.board.legal_plays(player, &remaining)?[0].len();
+        let mut board = self.board.clone();
+        board.apply_move(player, m)?;
+        let mut dices = self.dices;
+        dices.consume(dice)?;
+        if 1 + board.legal_plays(player, &dices.remaining())?[0].len() < max {
+            return Err(Error::MoveInvalidAllDices);
+        }
+
+        // if only one of two different dices can be played, it has to be the larger one
+        if max == 1 && remaining.len() == 2 && remaining[0] != remaining[1] {
+            let larger = remaining[0].max(remaining[1]);
+            if dice != larger && self.board.legal_plays(player, &[larger])?[0].len() == 1 {
+                return Err(Error::MoveInvalidLargerDice);
+            }
+        }
+
+        let hit = board.checkers(player.other())?[24] > self.board.checkers(player.other())?[24];
+        let action = Action::Move {
+            player,
+            checker_move: m,
+            dice,
+            hit,
+        };
+        self.undo_stack
+            .push((self.board.clone(), self.dices, action.clone()));
+        self.redo_stack.clear();
+        self.history.push(action);
+        self.board = board;
+        self.dices = dices;
+
+        Ok(self)
+    }
+
+    /// Play a whole turn at once and commit it.
+    ///
+    /// The moves are validated and applied as a single transaction: either the complete play is
+    /// legal and applied, or an error is returned and the game remains unchanged. The play has to
+    /// use as many dices as possible, see [`Game::legal_plays`]. A checker move spanning several
+    /// dices, e.g. `24/14` with a roll of 64, is played as consecutive steps of the same checker.
+    /// A turn already started with single moves has to be finished with them, or undone first.
+    /// ```
+    /// use backgammon::Game;
+    /// use backgammon::rules::{CheckerMove, Field, Player, Roll, ScriptedDices};
+    ///
+    /// let mut g = Game::new();
+    /// g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+    /// g.play(
+    ///     Player::Player0,
+    ///     &[
+    ///         CheckerMove::new(Field::Point(7), Field::Point(4)),
+    ///         CheckerMove::new(Field::Point(5), Field::Point(4)),
+    ///     ],
+    /// )?;
+    /// assert_eq!(g.who_plays, Player::Player1);
+    /// # Ok::<(), backgammon::Error>(())
+    /// ```
+    pub fn play(&mut self, player: Player, moves: &[CheckerMove]) -> Result<&mut Self, Error> {
+        self.turn_permitted(player)?;
+        if !self.undo_stack.is_empty()
+            || self.dices.consumed != Dices::from_values(self.dices.values).consumed
         {
             return Err(Error::MoveInvalid);
         }
 
-        // remove checker from old position
-        self.board.set(player, from, -1)?;
+        let mut game = self.apply_moves(player, moves)?;
+        if !game.turn_complete()? {
+            return Err(Error::MoveInvalidAllDices);
+        }
+        let _ = game.commit(player)?;
 
-        // move checker to new position, in case it is reaching the off position, set it off
-        let new_position = from as i8 - dice as i8;
-        if new_position < 0 {
-            self.board.set_off(player, 1)?;
-        } else {
-            self.board.set(player, new_position as usize, 1)?;
+        *self = game;
+        Ok(self)
+    }
+
+    /// Apply a sequence of checker moves to a copy of the game, trying all dices that could be
+    /// used for each move
+    pub(crate) fn apply_moves(&self, player: Player, moves: &[CheckerMove]) -> Result<Game, Error> {
+        let Some((m, rest)) = moves.split_first() else {
+            return Ok(self.clone());
+        };
+
+        let mut candidates = self.dices.remaining();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.retain(|&dice| match (m.from, m.to) {
+            (Field::Bar, Field::Point(to)) => to + dice as usize == 24,
+            (Field::Point(from), Field::Point(to)) => to + dice as usize == from,
+            (Field::Point(from), Field::Off) => dice as usize > from,
+            _ => false,
+        });
+
+        let mut error = Error::MoveInvalid;
+        for dice in candidates {
+            let mut game = self.clone();
+            match game
+                .play_step(player, dice, m.from)
+                .and_then(|g| g.apply_moves(player, rest))
+            {
+                Ok(game) => return Ok(game),
+                Err(e) => error = e,
+            }
         }
 
-        // set dice value to consumed
-        if dice == self.dices.values.0 && !self.dices.consumed.0 {
-            self.dices.consumed.0 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.1 {
-            self.dices.consumed.1 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.2 {
-            self.dices.consumed.2 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.3 {
-            self.dices.consumed.3 = true;
+        // a move spanning several dices is played as consecutive steps of the same checker
+        let mut dices = self.dices.remaining();
+        dices.sort_unstable();
+        dices.dedup();
+        for dice in dices {
+            let via = match (m.from, m.to) {
+                (Field::Bar, Field::Point(to)) if 24 - dice as usize > to => 24 - dice as usize,
+                (Field::Point(from), Field::Point(to)) if from > dice as usize + to => {
+                    from - dice as usize
+                }
+                (Field::Point(from), Field::Off) if from >= dice as usize => from - dice as usize,
+                _ => continue,
+            };
+            let mut moves = vec![
+                CheckerMove::new(m.from, Field::Point(via)),
+                CheckerMove::new(Field::Point(via), m.to),
+            ];
+            moves.extend_from_slice(rest);
+            if let Ok(game) = self.apply_moves(player, &moves) {
+                return Ok(game);
+            }
         }
+        Err(error)
+    }
 
-        // switch to other player if all dices have been consumed
-        if self.dices.consumed.0
-            && self.dices.consumed.1
-            && self.dices.consumed.2
-            && self.dices.consumed.3
-        {
-            self.who_plays = self.who_plays.other();
-            self.roll_first = true;
+    /// Undo the last checker move of the current turn, restoring the board, including hit
+    /// checkers, and the dices. Moves can be undone until the turn is committed.
+    pub fn undo(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.turn_permitted(player)?;
+
+        let (board, dices, action) = self.undo_stack.pop().ok_or(Error::NothingToUndo)?;
+        self.redo_stack.push((
+            std::mem::replace(&mut self.board, board),
+            std::mem::replace(&mut self.dices, dices),
+            action,
+        ));
+        let _ = self.history.pop();
+        Ok(self)
+    }
+
+    /// Redo the last undone checker move of the current turn. Redoing is no longer possible once
+    /// another checker has been moved.
+    pub fn redo(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.turn_permitted(player)?;
+
+        let (board, dices, action) = self.redo_stack.pop().ok_or(Error::NothingToRedo)?;
+        self.undo_stack.push((
+            std::mem::replace(&mut self.board, board),
+            std::mem::replace(&mut self.dices, dices),
+            action.clone(),
+        ));
+        self.history.push(action);
+        Ok(self)
+    }
+
+    /// Commit the moves of the current turn and pass the turn to the opponent.
+    ///
+    /// The turn can only be committed once no further checker can be moved, either because all
+    /// dices have been consumed or because no legal move is left. If the player has borne off all
+    /// checkers, the game ends.
+    pub fn commit(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.turn_permitted(player)?;
+        if !self.turn_complete()? {
+            return Err(Error::MoveFirst);
+        }
+
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.history.push(Action::Commit { player });
+
+        // the game ends as soon as a player has borne off all checkers
+        if self.board.off(player)? == 15 {
+            let result = self.board_result(player)?;
+            self.end_game(player, result);
+            return Ok(self);
         }
 
+        self.who_plays = self.who_plays.other();
+        self.roll_first = true;
         Ok(self)
     }
 
-    fn move_checker_from_bar(&mut self, player: Player, dice: u8) -> Result<&mut Self, Error> {
-        // check if move is permitted
-        let _ = self.move_permitted(player, dice)?;
+    /// Implements checks to validate if the player is in the middle of the turn
+    fn turn_permitted(&self, player: Player) -> Result<(), Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if player != self.who_plays {
+            return Err(Error::NotYourTurn);
+        }
+        if self.who_plays == Player::Nobody || self.roll_first {
+            return Err(Error::RollFirst);
+        }
+        Ok(())
+    }
 
-        // check if the dice value has been consumed
-        if (dice == self.dices.values.0 && self.dices.consumed.0)
-            || (dice == self.dices.values.1 && self.dices.consumed.1)
-            || (dice == self.dices.values.1 && self.dices.consumed.2)
-            || (dice == self.dices.values.1 && self.dices.consumed.3)
+    /// Check if no further move is possible in the current turn, either because all dices have
+    /// been consumed or because no checker can be moved.
+    pub(crate) fn turn_complete(&self) -> Result<bool, Error> {
+        let remaining = self.dices.remaining();
+        Ok(remaining.is_empty()
+            || self.board.legal_plays(self.who_plays, &remaining)?[0].is_empty())
+    }
+
+    /// End the game, recording the winner and the result
+    fn end_game(&mut self, winner: Player, result: GameResult) {
+        self.winner = winner;
+        self.result = Some(result);
+        self.who_plays = Player::Nobody;
+        self.history.push(Action::GameEnd { winner, result });
+    }
+
+    /// Compute the result of the game from the checkers the loser has left on the board
+    pub(crate) fn board_result(&self, winner: Player) -> Result<GameResult, Error> {
+        let loser = winner.other();
+        if self.board.off(loser)? > 0 {
+            Ok(GameResult::Single)
+        } else if self.board.checkers_in_opponent_home(loser)? > 0 {
+            Ok(GameResult::Backgammon)
+        } else {
+            Ok(GameResult::Gammon)
+        }
+    }
+
+    /// Apply the Murphy rule after a tied opening roll: the cube is doubled automatically and
+    /// stays in the middle, as long as the limit of automatic doubles is not reached.
+    fn murphy_double(&mut self) -> Result<(), Error> {
+        if !self.rules.murphy
+            || (self.rules.murphy_limit > 0 && self.murphy_doubles >= self.rules.murphy_limit)
         {
-            return Err(Error::MoveInvalid);
+            return Ok(());
+        }
+
+        if let Some(value) = self.cube.value().checked_mul(2) {
+            self.cube.set(value)?;
+            self.murphy_doubles = self.murphy_doubles.saturating_add(1);
+            self.history.push(Action::MurphyDouble { value });
         }
+        Ok(())
+    }
 
-        // set the checker from bar
-        self.board.set_bar(player, -1)?;
-        self.board.set(player, 24 - dice as usize, 1)?;
+    /// Offer the doubling cube to the opponent.
+    ///
+    /// The player whose turn it is may double before rolling the dices, if the cube is in the
+    /// middle or owned by the player. The opponent then has to either accept or reject the double.
+    pub fn offer_double(&mut self, player: Player) -> Result<&mut Self, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if self.cube_received {
+            return Err(Error::CubeReceived);
+        }
+        if self.who_plays == Player::Nobody || !self.roll_first {
+            return Err(Error::DoublingNotPermitted);
+        }
+        if player != self.who_plays {
+            return Err(Error::NotYourTurn);
+        }
 
-        // set dice value to consumed
-        if dice == self.dices.values.0 && !self.dices.consumed.0 {
-            self.dices.consumed.0 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.1 {
-            self.dices.consumed.1 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.2 {
-            self.dices.consumed.2 = true;
-        } else if dice == self.dices.values.1 && !self.dices.consumed.3 {
-            self.dices.consumed.3 = true;
+        // no doubling in the Crawford game, and under the Holland rule only after both players
+        // have rolled twice in the games after it
+        if self.crawford || (self.post_crawford && self.rules.holland && self.since_crawford < 4) {
+            return Err(Error::DoublingNotPermitted);
         }
 
-        // switch to other player if all dices have been consumed
-        if self.dices.consumed.0
-            && self.dices.consumed.1
-            && self.dices.consumed.2
-            && self.dices.consumed.3
+        let value = self.cube.offer(player.other())?;
+        self.cube_received = true;
+        self.history.push(Action::Double { player, value });
+        Ok(self)
+    }
+
+    /// Accept the doubling cube offered by the opponent. The value of the cube is doubled and the
+    /// accepting player becomes its owner.
+    pub fn accept_double(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.respond_permitted(player)?;
+
+        let value = self.cube.offer(player)?;
+        self.cube.set(value)?;
+        self.cube.set_owner(player);
+        self.cube_received = false;
+        self.history.push(Action::Take { player });
+        Ok(self)
+    }
+
+    /// Reject the doubling cube offered by the opponent. The game ends and the opponent wins the
+    /// value of the cube before doubling.
+    pub fn reject_double(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.respond_permitted(player)?;
+
+        self.cube_received = false;
+        self.history.push(Action::Drop { player });
+        self.end_game(player.other(), GameResult::Single);
+        Ok(self)
+    }
+
+    /// Beaver the doubling cube offered by the opponent: accept the double and immediately
+    /// redouble while keeping the cube.
+    ///
+    /// This is only permitted in money play and if the beaver rule is active. If the raccoon rule
+    /// is active, the opponent may then redouble again before rolling.
+    pub fn beaver(&mut self, player: Player) -> Result<&mut Self, Error> {
+        self.respond_permitted(player)?;
+        if !self.rules.beaver || !self.rules.money_play() {
+            return Err(Error::DoublingNotPermitted);
+        }
+
+        let value = self.cube.offer(player)?;
+        self.cube.set(value * 2)?;
+        self.cube.set_owner(player);
+        self.cube_received = false;
+        self.beaver_received = true;
+        self.history.push(Action::Beaver {
+            player,
+            value: value * 2,
+        });
+        Ok(self)
+    }
+
+    /// Raccoon a beaver of the opponent: redouble again, letting the opponent keep the cube.
+    ///
+    /// This is only permitted in money play, if the raccoon rule is active and before rolling the
+    /// dices.
+    pub fn raccoon(&mut self, player: Player) -> Result<&mut Self, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if !self.beaver_received
+            || !self.rules.raccoon
+            || !self.rules.money_play()
+            || !self.roll_first
         {
-            self.who_plays = self.who_plays.other();
-            self.roll_first = true;
+            return Err(Error::DoublingNotPermitted);
+        }
+        if player != self.who_plays {
+            return Err(Error::NotYourTurn);
+        }
+
+        self.cube.set(self.cube.value() * 2)?;
+        self.beaver_received = false;
+        self.history.push(Action::Raccoon {
+            player,
+            value: self.cube.value(),
+        });
+        Ok(self)
+    }
+
+    /// Implements checks to validate if the player is allowed to respond to a double
+    fn respond_permitted(&self, player: Player) -> Result<(), Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if !self.cube_received {
+            return Err(Error::CubeNotOffered);
+        }
+        if player != self.who_plays.other() {
+            return Err(Error::NotYourTurn);
+        }
+        Ok(())
+    }
+
+    /// Resign the game, conceding the given result to the opponent. The game ends immediately and
+    /// the opponent wins the result, multiplied by the value of the cube.
+    pub fn resign(&mut self, player: Player, result: GameResult) -> Result<&mut Self, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if player == Player::Nobody {
+            return Err(Error::PlayerInvalid);
         }
 
+        self.cube_received = false;
+        self.history.push(Action::Resign { player, result });
+        self.end_game(player.other(), result);
+        Ok(self)
+    }
+
+    /// Apply an action of the history of a game, e.g. to replay a game from its initial state.
+    ///
+    /// Each action is validated like the corresponding call, e.g. [`Game::offer_double`] for
+    /// [`Action::Double`], and has to be recorded identically, including the dices rolled and the
+    /// hits. [`Action::MurphyDouble`] and [`Action::GameEnd`] only check that the previous action
+    /// has recorded them, i.e. the tied opening roll or the action ending the game. Replaying the
+    /// history on the initial state gives the identical game, except that undone moves can no
+    /// longer be redone.
+    /// ```
+    /// use backgammon::Game;
+    /// use backgammon::rules::{CheckerMove, Field, Player, Roll, ScriptedDices};
+    ///
+    /// let mut g = Game::new();
+    /// g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+    /// g.play(
+    ///     Player::Player0,
+    ///     &[
+    ///         CheckerMove::new(Field::Point(7), Field::Point(4)),
+    ///         CheckerMove::new(Field::Point(5), Field::Point(4)),
+    ///     ],
+    /// )?;
+    ///
+    /// let mut replayed = Game::new();
+    /// for action in g.history() {
+    ///     replayed.apply(action)?;
+    /// }
+    /// assert_eq!(replayed, g);
+    /// # Ok::<(), backgammon::Error>(())
+    /// ```
+    pub fn apply(&mut self, action: &Action) -> Result<&mut Self, Error> {
+        let mut game = self.clone();
+        let _ = match *action {
+            Action::Roll { dices, .. } => game.roll_with(&mut ScriptedDices::new(vec![dices]))?,
+            Action::Move {
+                player,
+                checker_move,
+                dice,
+                ..
+            } => game.play_step(player, dice, checker_move.from)?,
+            Action::Commit { player } => game.commit(player)?,
+            Action::Double { player, .. } => game.offer_double(player)?,
+            Action::Take { player } => game.accept_double(player)?,
+            Action::Drop { player } => game.reject_double(player)?,
+            Action::Beaver { player, .. } => game.beaver(player)?,
+            Action::Raccoon { player, .. } => game.raccoon(player)?,
+            Action::Resign { player, result } => game.resign(player, result)?,
+            // automatic doubles and the end of the game are recorded by the action causing them
+            Action::MurphyDouble { .. } | Action::GameEnd { .. } => {
+                if self.history.last() != Some(action) {
+                    return Err(Error::ActionInvalid);
+                }
+                return Ok(self);
+            }
+        };
+
+        if game.history.get(self.history.len()) != Some(action) {
+            return Err(Error::ActionInvalid);
+        }
+        *self = game;
         Ok(self)
     }
 
+    /// Returns the points won by the winner, or 0 if the game has not ended yet.
+    ///
+    /// The result is multiplied by the value of the cube. If the Jacoby rule is active in money play,
+    /// gammons and backgammons count as single games as long as the cube has not been turned or
+    /// doubled automatically.
+    pub fn points(&self) -> u64 {
+        match self.result {
+            None => 0,
+            Some(result) => {
+                if self.rules.jacoby
+                    && self.rules.money_play()
+                    && self.cube.owner() == Player::Nobody
+                    && self.cube.value() == 1
+                {
+                    self.cube.value()
+                } else {
+                    self.cube.value() * result.multiplier()
+                }
+            }
+        }
+    }
+}
+
+impl Roll for Game {
+    fn roll_with(&mut self, source: &mut dyn DiceSource) -> Result<&mut Self, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+        if self.cube_received {
+            return Err(Error::CubeReceived);
+        }
+        if self.who_plays != Player::Nobody && !self.roll_first {
+            if self.turn_complete()? {
+                return Err(Error::CommitFirst);
+            }
+            return Err(Error::MoveFirst);
+        }
+
+        self.dices = self.dices.roll_with(source)?;
+        if self.who_plays == Player::Nobody {
+            match self.dices.values.0.cmp(&self.dices.values.1) {
+                Ordering::Equal => {
+                    self.who_plays = Player::Nobody;
+                    self.history.push(Action::Roll {
+                        player: Player::Nobody,
+                        dices: self.dices.values,
+                    });
+                    self.murphy_double()?;
+                    return Ok(self);
+                }
+                Ordering::Greater => {
+                    self.who_plays = Player::Player0;
+                }
+                Ordering::Less => {
+                    self.who_plays = Player::Player1;
+                }
+            }
+        }
+        self.roll_first = false;
+        self.beaver_received = false;
+        self.history.push(Action::Roll {
+            player: self.who_plays,
+            dices: self.dices.values,
+        });
+        if self.post_crawford {
+            self.since_crawford = self.since_crawford.saturating_add(1);
+        }
+
+        Ok(self)
+    }
+}
+
+impl Move for Game {
+    fn move_checker(&mut self, player: Player, dice: u8, from: usize) -> Result<&mut Self, Error> {
+        self.play_step(player, dice, Field::Point(from))
+    }
+
+    fn move_checker_from_bar(&mut self, player: Player, dice: u8) -> Result<&mut Self, Error> {
+        self.play_step(player, dice, Field::Bar)
+    }
+
     /// Implements checks to validate if the player is allowed to move
     fn move_permitted(&mut self, player: Player, dice: u8) -> Result<&mut Self, Error> {
+        // check if game has already ended
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+
         // check if player is allowed to move
         if player != self.who_plays {
             return Err(Error::NotYourTurn);
@@ -247,6 +888,7 @@ impl GameRules for Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::{ScriptedDices, SeededDices};
 
     // Test Display trait for Game
     #[test]
@@ -254,7 +896,10 @@ mod tests {
         let g = Game::new();
         assert_eq!(
             format!("{}", g),
-            "Rules: Points: 7, Beaver: false, Raccoon: false, Murphy: false, Murphy Limit: 0, Jacoby: false, Crawford: true, Holland: false\nDices: Dices { values: (0, 0), consumed: (false, false, false, false) }\nCube: 1\nCube owner: Nobody\nWho plays: Nobody\nBoard: BoardDisplay { board: [-2, 0, 0, 0, 0, 5, 0, 3, 0, 0, 0, -5, 5, 0, 0, 0, -3, 0, -5, 0, 0, 0, 0, 2], bar: (0, 0), off: (0, 0) }\nCrawford game: false\nSince Crawford game: 0\n"
+            format!(
+                "Rules: Points: 7, Beaver: false, Raccoon: false, Murphy: false, Murphy Limit: 0, Jacoby: false, Crawford: true, Holland: false\n{}Cube: 1\nPips: O 167, X 167\nCrawford game: false\nSince Crawford game: 0\n",
+                g.board.to_ascii(Player::Player0)
+            )
         );
     }
 
@@ -274,4 +919,992 @@ mod tests {
         assert!(g.rules.jacoby);
         assert!(g.rules.holland);
     }
+
+    #[test]
+    fn test_legal_plays() -> Result<(), Error> {
+        let mut g = Game::new();
+        assert!(g.legal_plays().is_err());
+
+        g.who_plays = Player::Player0;
+        g.dices = Dices {
+            values: (6, 6),
+            consumed: (true, false, false, false),
+        };
+        let plays = g.legal_plays()?;
+        assert!(!plays.is_empty());
+        assert!(plays.iter().all(|p| p.len() == 3));
+        Ok(())
+    }
+
+    /// Create a board with the given checkers per field for both players, all others are off
+    fn position(p0: &[(usize, u8)], p1: &[(usize, u8)]) -> Result<Board, Error> {
+        let mut board = Board::new();
+        let display = board.get().board;
+        for (field, &checkers) in display.iter().enumerate() {
+            if checkers > 0 {
+                board.set(Player::Player0, field, -checkers)?;
+            } else if checkers < 0 {
+                board.set(Player::Player1, 23 - field, checkers)?;
+            }
+        }
+        for (player, checkers) in [(Player::Player0, p0), (Player::Player1, p1)] {
+            for &(field, amount) in checkers {
+                board.set(player, field, amount as i8)?;
+            }
+            board.set_off(player, 15 - checkers.iter().map(|c| c.1).sum::<u8>())?;
+        }
+        Ok(board)
+    }
+
+    /// Create a game where player 0 has rolled the given dices
+    fn rolled(board: Board, values: (u8, u8)) -> Game {
+        let mut g = Game::new();
+        g.board = board;
+        g.who_plays = Player::Player0;
+        g.dices = Dices {
+            values,
+            consumed: (false, false, values.0 != values.1, values.0 != values.1),
+        };
+        g
+    }
+
+    #[test]
+    fn test_roll() -> Result<(), Error> {
+        let mut g = Game::new();
+        while g.who_plays == Player::Nobody {
+            let _ = g.roll()?;
+        }
+        assert_ne!(g.dices.values.0, g.dices.values.1);
+        assert!(g.roll().is_err());
+        assert!(!g.legal_plays()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (3, 1));
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        assert!(g.move_checker(Player::Player0, 3, 7).is_err());
+        assert!(g.move_checker(Player::Player1, 1, 5).is_err());
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        assert_eq!(g.board.get().board[4], 2);
+        assert!(matches!(g.roll(), Err(Error::CommitFirst)));
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        assert!(g.move_checker(Player::Player1, 1, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker_doubles() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (2, 2));
+        for _i in 0..3 {
+            let _ = g.move_checker(Player::Player0, 2, 12)?;
+            assert_eq!(g.who_plays, Player::Player0);
+        }
+        let _ = g.move_checker(Player::Player0, 2, 12)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker_all_dices() -> Result<(), Error> {
+        let board = position(&[(11, 1), (4, 1), (1, 13)], &[(21, 2), (5, 13)])?;
+        let mut g = rolled(board, (6, 3));
+        assert!(matches!(
+            g.move_checker(Player::Player0, 3, 11),
+            Err(Error::MoveInvalidAllDices)
+        ));
+        let _ = g.move_checker(Player::Player0, 3, 4)?;
+        let _ = g.move_checker(Player::Player0, 6, 11)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker_larger_dice() -> Result<(), Error> {
+        let board = position(&[(8, 1), (0, 14)], &[(22, 2), (12, 13)])?;
+        let mut g = rolled(board, (2, 5));
+        assert!(matches!(
+            g.move_checker(Player::Player0, 2, 8),
+            Err(Error::MoveInvalidLargerDice)
+        ));
+        let _ = g.move_checker(Player::Player0, 5, 8)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        assert!(g.roll_first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker_from_bar() -> Result<(), Error> {
+        let board = position(&[(5, 14)], &[(5, 2), (4, 2), (0, 11)])?;
+        let mut g = rolled(board, (6, 5));
+        g.board.set_bar(Player::Player0, 1)?;
+        assert!(matches!(
+            g.move_checker(Player::Player0, 6, 5),
+            Err(Error::MoveInvalidBar)
+        ));
+        assert!(g.move_checker_from_bar(Player::Player0, 6).is_err());
+        assert!(g.move_checker_from_bar(Player::Player0, 5).is_err());
+        assert!(g.turn_complete()?);
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_checker_bear_off() -> Result<(), Error> {
+        let board = position(&[(7, 1), (3, 10), (1, 4)], &[(12, 15)])?;
+        let mut g = rolled(board, (4, 6));
+        assert!(matches!(
+            g.move_checker(Player::Player0, 4, 3),
+            Err(Error::MoveInvalidOff)
+        ));
+        let _ = g.move_checker(Player::Player0, 4, 7)?;
+        assert!(matches!(
+            g.move_checker(Player::Player0, 6, 1),
+            Err(Error::MoveInvalidOff)
+        ));
+        let _ = g.move_checker(Player::Player0, 6, 3)?;
+        assert_eq!(g.board.get().off.0, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_over_single() -> Result<(), Error> {
+        let board = position(&[(0, 2)], &[(3, 3)])?;
+        let mut g = rolled(board, (1, 1));
+        let _ = g.move_checker(Player::Player0, 1, 0)?;
+        assert!(matches!(g.commit(Player::Player0), Err(Error::MoveFirst)));
+        assert_eq!(g.points(), 0);
+        let _ = g.move_checker(Player::Player0, 1, 0)?;
+        assert_eq!(g.result, None);
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.winner, Player::Player0);
+        assert_eq!(g.result, Some(GameResult::Single));
+        assert_eq!(g.who_plays, Player::Nobody);
+        assert_eq!(g.points(), 1);
+        assert!(matches!(g.roll(), Err(Error::GameEnded)));
+        assert!(matches!(
+            g.move_checker(Player::Player0, 1, 0),
+            Err(Error::GameEnded)
+        ));
+        assert!(matches!(g.legal_plays(), Err(Error::GameEnded)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_over_gammon() -> Result<(), Error> {
+        let board = position(&[(1, 1)], &[(5, 15)])?;
+        let mut g = rolled(board, (6, 2));
+        g.cube.set(2)?;
+        g.cube.set_owner(Player::Player1);
+        let _ = g.move_checker(Player::Player0, 6, 1)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.result, Some(GameResult::Gammon));
+        assert_eq!(g.points(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_over_backgammon() -> Result<(), Error> {
+        let board = position(&[(1, 1)], &[(5, 14), (20, 1)])?;
+        let mut g = rolled(board, (6, 2));
+        let _ = g.move_checker(Player::Player0, 6, 1)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.result, Some(GameResult::Backgammon));
+        assert_eq!(g.points(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_over_jacoby() -> Result<(), Error> {
+        let board = position(&[(1, 1)], &[(5, 14), (20, 1)])?;
+        let mut g = rolled(board.clone(), (6, 2)).with_jacoby();
+        g.rules.points = 0;
+        let _ = g.move_checker(Player::Player0, 6, 1)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.result, Some(GameResult::Backgammon));
+        assert_eq!(g.points(), 1);
+
+        // the Jacoby rule does not apply after an automatic double
+        let mut g = rolled(board.clone(), (6, 2)).with_jacoby();
+        g.rules.points = 0;
+        g.cube.set(2)?;
+        let _ = g.move_checker(Player::Player0, 6, 1)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.points(), 6);
+
+        // nor in match play
+        let board = position(&[(1, 1)], &[(5, 15)])?;
+        let mut g = rolled(board, (6, 2)).with_jacoby();
+        let _ = g.move_checker(Player::Player0, 6, 1)?;
+        let _ = g.commit(Player::Player0)?;
+        assert_eq!(g.result, Some(GameResult::Gammon));
+        assert_eq!(g.points(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_result_display() {
+        assert_eq!(format!("{}", GameResult::Single), "Single");
+        assert_eq!(format!("{}", GameResult::Gammon), "Gammon");
+        assert_eq!(format!("{}", GameResult::Backgammon), "Backgammon");
+    }
+
+    #[test]
+    fn test_double_accept() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (3, 1));
+        assert!(matches!(
+            g.offer_double(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.commit(Player::Player0)?;
+
+        assert!(matches!(
+            g.offer_double(Player::Player0),
+            Err(Error::NotYourTurn)
+        ));
+        assert!(matches!(
+            g.accept_double(Player::Player0),
+            Err(Error::CubeNotOffered)
+        ));
+        let _ = g.offer_double(Player::Player1)?;
+        assert!(matches!(
+            g.offer_double(Player::Player1),
+            Err(Error::CubeReceived)
+        ));
+        assert!(matches!(g.roll(), Err(Error::CubeReceived)));
+        assert!(matches!(
+            g.accept_double(Player::Player1),
+            Err(Error::NotYourTurn)
+        ));
+        let _ = g.accept_double(Player::Player0)?;
+        assert_eq!(g.cube.value(), 2);
+        assert_eq!(g.cube.owner(), Player::Player0);
+
+        // the cube is now owned by the opponent
+        assert!(matches!(
+            g.offer_double(Player::Player1),
+            Err(Error::DoublingNotPermitted)
+        ));
+        let _ = g.roll()?;
+        assert!(matches!(
+            g.offer_double(Player::Player1),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_redouble() -> Result<(), Error> {
+        let mut g = Game::new();
+        g.who_plays = Player::Player1;
+        g.roll_first = true;
+        let _ = g.offer_double(Player::Player1)?;
+        let _ = g.accept_double(Player::Player0)?;
+        g.who_plays = Player::Player0;
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.accept_double(Player::Player1)?;
+        assert_eq!(g.cube.value(), 4);
+        assert_eq!(g.cube.owner(), Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_reject() -> Result<(), Error> {
+        let mut g = Game::new();
+        g.who_plays = Player::Player0;
+        g.roll_first = true;
+        g.cube.set(2)?;
+        g.cube.set_owner(Player::Player0);
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.reject_double(Player::Player1)?;
+        assert_eq!(g.winner, Player::Player0);
+        assert_eq!(g.result, Some(GameResult::Single));
+        assert_eq!(g.points(), 2);
+        assert!(matches!(
+            g.offer_double(Player::Player0),
+            Err(Error::GameEnded)
+        ));
+        assert!(matches!(g.roll(), Err(Error::GameEnded)));
+        Ok(())
+    }
+
+    /// Create a money game where player 0 is about to double
+    fn money_game() -> Game {
+        let mut g = Game::new().with_beaver().with_raccoon();
+        g.rules.points = 0;
+        g.who_plays = Player::Player0;
+        g.roll_first = true;
+        g
+    }
+
+    #[test]
+    fn test_beaver() -> Result<(), Error> {
+        let mut g = money_game();
+        assert!(matches!(
+            g.beaver(Player::Player1),
+            Err(Error::CubeNotOffered)
+        ));
+        let _ = g.offer_double(Player::Player0)?;
+        assert!(matches!(g.beaver(Player::Player0), Err(Error::NotYourTurn)));
+        let _ = g.beaver(Player::Player1)?;
+        assert_eq!(g.cube.value(), 4);
+        assert_eq!(g.cube.owner(), Player::Player1);
+        assert_eq!(g.who_plays, Player::Player0);
+        assert!(matches!(
+            g.offer_double(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_raccoon() -> Result<(), Error> {
+        let mut g = money_game();
+        assert!(matches!(
+            g.raccoon(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.beaver(Player::Player1)?;
+        assert!(matches!(
+            g.raccoon(Player::Player1),
+            Err(Error::NotYourTurn)
+        ));
+        let _ = g.raccoon(Player::Player0)?;
+        assert_eq!(g.cube.value(), 8);
+        assert_eq!(g.cube.owner(), Player::Player1);
+        assert!(matches!(
+            g.raccoon(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_raccoon_after_roll() -> Result<(), Error> {
+        let mut g = money_game();
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.beaver(Player::Player1)?;
+        let _ = g.roll()?;
+        assert!(matches!(
+            g.raccoon(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_beaver_not_permitted() -> Result<(), Error> {
+        // beaver rule not active
+        let mut g = money_game();
+        g.rules.beaver = false;
+        let _ = g.offer_double(Player::Player0)?;
+        assert!(matches!(
+            g.beaver(Player::Player1),
+            Err(Error::DoublingNotPermitted)
+        ));
+
+        // match play
+        let mut g = money_game();
+        g.rules.points = 7;
+        let _ = g.offer_double(Player::Player0)?;
+        assert!(matches!(
+            g.beaver(Player::Player1),
+            Err(Error::DoublingNotPermitted)
+        ));
+        let _ = g.accept_double(Player::Player1)?;
+        assert_eq!(g.cube.value(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_raccoon_not_permitted() -> Result<(), Error> {
+        let mut g = money_game();
+        g.rules.raccoon = false;
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.beaver(Player::Player1)?;
+        assert!(matches!(
+            g.raccoon(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_murphy() -> Result<(), Error> {
+        let mut g = Game::new().with_murphy(2);
+        g.murphy_double()?;
+        assert_eq!(g.cube.value(), 2);
+        assert_eq!(g.cube.owner(), Player::Nobody);
+        assert_eq!(g.murphy_doubles, 1);
+        g.murphy_double()?;
+        g.murphy_double()?;
+        assert_eq!(g.cube.value(), 4);
+        assert_eq!(g.murphy_doubles, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_murphy_unlimited() -> Result<(), Error> {
+        let mut g = Game::new().with_murphy(0);
+        for _i in 0..5 {
+            g.murphy_double()?;
+        }
+        assert_eq!(g.cube.value(), 32);
+        assert_eq!(g.murphy_doubles, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_murphy_inactive() -> Result<(), Error> {
+        let mut g = Game::new();
+        g.murphy_double()?;
+        assert_eq!(g.cube.value(), 1);
+        assert_eq!(g.murphy_doubles, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_murphy_roll() -> Result<(), Error> {
+        let mut g = Game::new().with_murphy(0);
+        let mut ties = 0;
+        while g.who_plays == Player::Nobody {
+            let _ = g.roll()?;
+            if g.dices.values.0 == g.dices.values.1 {
+                ties += 1;
+            }
+        }
+        assert_eq!(g.murphy_doubles, ties);
+        assert_eq!(g.cube.value(), 2u64.pow(ties as u32));
+        Ok(())
+    }
+
+    #[test]
+    fn test_crawford() -> Result<(), Error> {
+        let mut g = Game::new();
+        g.set_crawford(true, false);
+        g.who_plays = Player::Player0;
+        g.roll_first = true;
+        assert!(g.crawford());
+        assert!(matches!(
+            g.offer_double(Player::Player0),
+            Err(Error::DoublingNotPermitted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_post_crawford() -> Result<(), Error> {
+        let mut g = Game::new();
+        g.set_crawford(false, true);
+        g.who_plays = Player::Player0;
+        g.roll_first = true;
+        assert!(g.post_crawford());
+        let _ = g.offer_double(Player::Player0)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_holland() -> Result<(), Error> {
+        let mut g = Game::new().with_holland();
+        g.set_crawford(false, true);
+        for rolls in 0..6 {
+            g.who_plays = Player::Player0;
+            g.roll_first = true;
+            if rolls < 4 {
+                assert!(matches!(
+                    g.offer_double(Player::Player0),
+                    Err(Error::DoublingNotPermitted)
+                ));
+            } else {
+                let _ = g.offer_double(Player::Player0)?;
+                break;
+            }
+            let _ = g.roll()?;
+        }
+        assert_eq!(g.since_crawford, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_with() -> Result<(), Error> {
+        let mut source = ScriptedDices::new(vec![(2, 2), (5, 6), (6, 4)]);
+        let mut g = Game::new();
+        let _ = g.roll_with(&mut source)?;
+        assert_eq!(g.who_plays, Player::Nobody);
+        let _ = g.roll_with(&mut source)?;
+        assert_eq!(g.who_plays, Player::Player1);
+        assert_eq!(g.dices.values, (5, 6));
+        let _ = g.move_checker(Player::Player1, 6, 23)?;
+        let _ = g.move_checker(Player::Player1, 5, 12)?;
+        let _ = g.commit(Player::Player1)?;
+        let _ = g.roll_with(&mut source)?;
+        assert_eq!(g.who_plays, Player::Player0);
+        assert_eq!(g.dices.values, (6, 4));
+        assert!(matches!(g.roll_with(&mut source), Err(Error::MoveFirst)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_with_seeded() -> Result<(), Error> {
+        let mut a = Game::new();
+        let mut b = Game::new();
+        let mut source_a = SeededDices::new(7);
+        let mut source_b = SeededDices::new(7);
+        while a.who_plays == Player::Nobody {
+            let _ = a.roll_with(&mut source_a)?;
+            let _ = b.roll_with(&mut source_b)?;
+            assert_eq!(a.dices, b.dices);
+        }
+        assert_eq!(a.who_plays, b.who_plays);
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_redo() -> Result<(), Error> {
+        let board = position(&[(7, 3), (5, 12)], &[(20, 1), (3, 14)])?;
+        let mut g = rolled(board.clone(), (4, 2));
+        assert!(matches!(g.undo(Player::Player0), Err(Error::NothingToUndo)));
+
+        // hit the opponent's checker
+        let _ = g.move_checker(Player::Player0, 4, 7)?;
+        let hit = g.board.clone();
+        assert_eq!(hit.get().bar.1, 1);
+        let _ = g.move_checker(Player::Player0, 2, 5)?;
+        let played = g.board.clone();
+
+        assert!(matches!(g.undo(Player::Player1), Err(Error::NotYourTurn)));
+        let _ = g.undo(Player::Player0)?;
+        assert_eq!(g.board, hit);
+        assert_eq!(g.dices.remaining(), vec![2]);
+        let _ = g.undo(Player::Player0)?;
+        assert_eq!(g.board, board);
+        assert_eq!(g.board.get().bar.1, 0);
+        assert_eq!(g.dices.remaining(), vec![4, 2]);
+
+        let _ = g.redo(Player::Player0)?;
+        let _ = g.redo(Player::Player0)?;
+        assert_eq!(g.board, played);
+        assert!(g.dices.remaining().is_empty());
+        assert!(matches!(g.redo(Player::Player0), Err(Error::NothingToRedo)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_history() -> Result<(), Error> {
+        let mut g = Game::new();
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1), (5, 2)]))?;
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.undo(Player::Player0)?;
+        let _ = g.undo(Player::Player0)?;
+        let _ = g.redo(Player::Player0)?;
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.commit(Player::Player0)?;
+        let _ = g.offer_double(Player::Player1)?;
+        let _ = g.accept_double(Player::Player0)?;
+
+        let m = |from, to| Action::Move {
+            player: Player::Player0,
+            checker_move: CheckerMove::new(Field::Point(from), Field::Point(to)),
+            dice: (from - to) as u8,
+            hit: false,
+        };
+        assert_eq!(
+            g.history(),
+            &[
+                Action::Roll {
+                    player: Player::Player0,
+                    dices: (3, 1)
+                },
+                m(7, 4),
+                m(5, 4),
+                Action::Commit {
+                    player: Player::Player0
+                },
+                Action::Double {
+                    player: Player::Player1,
+                    value: 2
+                },
+                Action::Take {
+                    player: Player::Player0
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_hit_and_resign() -> Result<(), Error> {
+        let mut g = Game::new();
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(6, 4), (2, 1)]))?;
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(12), Field::Point(8)),
+            ],
+        )?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 1)]))?;
+        let _ = g.play(
+            Player::Player1,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(6)),
+                CheckerMove::new(Field::Point(12), Field::Point(10)),
+            ],
+        )?;
+        assert!(g.history().contains(&Action::Move {
+            player: Player::Player1,
+            checker_move: CheckerMove::new(Field::Point(7), Field::Point(6)),
+            dice: 1,
+            hit: true,
+        }));
+
+        assert!(matches!(
+            g.resign(Player::Nobody, GameResult::Single),
+            Err(Error::PlayerInvalid)
+        ));
+        let _ = g.resign(Player::Player0, GameResult::Gammon)?;
+        assert_eq!(g.winner, Player::Player1);
+        assert_eq!(g.points(), 2);
+        assert_eq!(
+            g.history()[g.history().len() - 2..],
+            [
+                Action::Resign {
+                    player: Player::Player0,
+                    result: GameResult::Gammon
+                },
+                Action::GameEnd {
+                    winner: Player::Player1,
+                    result: GameResult::Gammon
+                },
+            ]
+        );
+        assert!(matches!(
+            g.resign(Player::Player1, GameResult::Single),
+            Err(Error::GameEnded)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay() -> Result<(), Error> {
+        let mut g = Game::new().with_beaver().with_raccoon();
+        g.rules.points = 0;
+        let start = g.clone();
+
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 3), (6, 4)]))?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(6, 4)]))?;
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(12), Field::Point(8)),
+            ],
+        )?;
+        let _ = g.offer_double(Player::Player1)?;
+        let _ = g.beaver(Player::Player0)?;
+        let _ = g.raccoon(Player::Player1)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 1)]))?;
+        let _ = g.move_checker(Player::Player1, 1, 7)?;
+        let _ = g.move_checker(Player::Player1, 2, 12)?;
+        let _ = g.undo(Player::Player1)?;
+        let _ = g.move_checker(Player::Player1, 2, 12)?;
+        let _ = g.commit(Player::Player1)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(5, 5)]))?;
+
+        // replay the running game, and the game after it ended
+        let mut replayed = start.clone();
+        for action in g.history() {
+            let _ = replayed.apply(action)?;
+        }
+        assert_eq!(replayed, g);
+
+        let _ = g.resign(Player::Player0, GameResult::Single)?;
+        let mut replayed = start.clone();
+        for action in g.history() {
+            let _ = replayed.apply(action)?;
+        }
+        assert_eq!(replayed, g);
+        assert_eq!(replayed.points(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_murphy() -> Result<(), Error> {
+        let mut g = Game::new().with_murphy(0);
+        let start = g.clone();
+
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 2)]))?;
+        assert_eq!(
+            g.history(),
+            [
+                Action::Roll {
+                    player: Player::Nobody,
+                    dices: (2, 2)
+                },
+                Action::MurphyDouble { value: 2 },
+            ]
+        );
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.undo(Player::Player0)?;
+
+        // the move which can still be redone is not part of the replayed game
+        let mut replayed = start.clone();
+        for action in g.history() {
+            let _ = replayed.apply(action)?;
+        }
+        assert_eq!(replayed, g);
+        assert_eq!(replayed.cube.value(), 2);
+        assert!(matches!(
+            replayed.redo(Player::Player0),
+            Err(Error::NothingToRedo)
+        ));
+        let _ = replayed.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.redo(Player::Player0)?;
+        assert_eq!(replayed, g);
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_serde() -> Result<(), Error> {
+        let mut g = Game::new().with_murphy(0);
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(4, 4), (6, 4)]))?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(6, 4)]))?;
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(12), Field::Point(8)),
+            ],
+        )?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 1)]))?;
+        let _ = g.move_checker(Player::Player1, 1, 7)?;
+        let _ = g.undo(Player::Player1)?;
+
+        let json = serde_json::to_string(g.history()).unwrap();
+        let history: Vec<Action> = serde_json::from_str(&json).unwrap();
+        assert_eq!(history, g.history());
+
+        let json = serde_json::to_string(&g).unwrap();
+        let deserialized: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, g);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_0_6_0() {
+        // a new game as serialized by version 0.6.0
+        let json = r#"{"rules":{"points":7,"beaver":false,"raccoon":false,"murphy":false,"murphy_limit":0,"jacoby":false,"crawford":true,"holland":false},"dices":{"values":[0,0],"consumed":[false,false,false,false]},"who_plays":"Nobody","board":{"raw_board":[{"board":[0,0,0,0,0,5,0,3,0,0,0,0,5,0,0,0,0,0,0,0,0,0,0,2],"bar":0,"off":0},{"board":[0,0,0,0,0,5,0,3,0,0,0,0,5,0,0,0,0,0,0,0,0,0,0,2],"bar":0,"off":0}]},"cube":{"exponential":0,"owner":"Nobody"},"crawford":false,"since_crawford":0,"roll_first":false,"cube_received":false}"#;
+        let game: Game = serde_json::from_str(json).unwrap();
+        assert_eq!(game, Game::new());
+    }
+
+    #[test]
+    fn test_apply_invalid() -> Result<(), Error> {
+        let mut g = Game::new();
+        assert!(matches!(
+            g.apply(&Action::Roll {
+                player: Player::Player1,
+                dices: (6, 4)
+            }),
+            Err(Error::ActionInvalid)
+        ));
+        assert!(matches!(
+            g.apply(&Action::GameEnd {
+                winner: Player::Player0,
+                result: GameResult::Single
+            }),
+            Err(Error::ActionInvalid)
+        ));
+        let _ = g.apply(&Action::Roll {
+            player: Player::Player0,
+            dices: (6, 4),
+        })?;
+        assert!(matches!(
+            g.apply(&Action::Move {
+                player: Player::Player0,
+                checker_move: CheckerMove::new(Field::Point(23), Field::Point(17)),
+                dice: 6,
+                hit: true,
+            }),
+            Err(Error::ActionInvalid)
+        ));
+        assert!(matches!(
+            g.apply(&Action::Commit {
+                player: Player::Player0
+            }),
+            Err(Error::MoveFirst)
+        ));
+        assert_eq!(g.history().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_clears_redo() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (3, 1));
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        let _ = g.undo(Player::Player0)?;
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        assert!(matches!(g.redo(Player::Player0), Err(Error::NothingToRedo)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_after_commit() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (3, 1));
+        let _ = g.move_checker(Player::Player0, 3, 7)?;
+        assert!(matches!(g.commit(Player::Player0), Err(Error::MoveFirst)));
+        let _ = g.move_checker(Player::Player0, 1, 5)?;
+        let _ = g.commit(Player::Player0)?;
+        assert!(matches!(g.undo(Player::Player0), Err(Error::NotYourTurn)));
+        assert!(matches!(g.undo(Player::Player1), Err(Error::RollFirst)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_play() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (6, 5));
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(17), Field::Point(12)),
+            ],
+        )?;
+        assert_eq!(g.board.get().board[23], 1);
+        assert_eq!(g.board.get().board[12], 6);
+        assert_eq!(g.who_plays, Player::Player1);
+        assert!(g.roll_first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_incomplete() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (6, 5));
+        let before = g.clone();
+        assert!(matches!(
+            g.play(
+                Player::Player0,
+                &[CheckerMove::new(Field::Point(23), Field::Point(17))]
+            ),
+            Err(Error::MoveInvalidAllDices)
+        ));
+        assert_eq!(g.board, before.board);
+        assert_eq!(g.dices, before.dices);
+        assert_eq!(g.who_plays, Player::Player0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_started_turn() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (6, 5));
+        let _ = g.move_checker(Player::Player0, 6, 23)?;
+        let before = g.clone();
+        assert!(matches!(
+            g.play(
+                Player::Player0,
+                &[CheckerMove::new(Field::Point(12), Field::Point(7))]
+            ),
+            Err(Error::MoveInvalid)
+        ));
+        assert_eq!(g, before);
+
+        // the whole turn can be played again after undoing the single move
+        let _ = g.undo(Player::Player0)?;
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(12), Field::Point(7)),
+            ],
+        )?;
+        assert_eq!(g.who_plays, Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_spanning_dices() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (6, 4));
+        let _ = g.play(
+            Player::Player0,
+            &[CheckerMove::new(Field::Point(23), Field::Point(13))],
+        )?;
+        assert_eq!(g.board.get().board[13], 1);
+        assert_eq!(g.board.get().board[23], 1);
+        assert_eq!(g.history().len(), 3);
+
+        let mut g = rolled(Board::new(), (2, 2));
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(12), Field::Point(8)),
+                CheckerMove::new(Field::Point(12), Field::Point(8)),
+            ],
+        )?;
+        assert_eq!(g.board.get().board[8], 2);
+
+        // a single checker can not use the dices of a different roll
+        let mut g = rolled(Board::new(), (6, 4));
+        assert!(g
+            .play(
+                Player::Player0,
+                &[CheckerMove::new(Field::Point(23), Field::Point(14))],
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_invalid() -> Result<(), Error> {
+        let mut g = rolled(Board::new(), (6, 5));
+        let before = g.clone();
+        assert!(g
+            .play(
+                Player::Player0,
+                &[
+                    CheckerMove::new(Field::Point(12), Field::Point(6)),
+                    CheckerMove::new(Field::Point(12), Field::Point(8)),
+                ],
+            )
+            .is_err());
+        assert_eq!(g.board, before.board);
+        assert_eq!(g.dices, before.dices);
+        assert!(g
+            .play(
+                Player::Player1,
+                &[
+                    CheckerMove::new(Field::Point(12), Field::Point(6)),
+                    CheckerMove::new(Field::Point(12), Field::Point(7)),
+                ],
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_bear_off() -> Result<(), Error> {
+        let board = position(&[(4, 1), (3, 1), (0, 2)], &[(12, 15)])?;
+        let mut g = rolled(board, (4, 6));
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(4), Field::Point(0)),
+                CheckerMove::new(Field::Point(3), Field::Off),
+            ],
+        )?;
+        assert_eq!(g.board.get().off.0, 12);
+        assert_eq!(g.board.get().board[0], 3);
+        Ok(())
+    }
 }
@@ -0,0 +1,633 @@
+use crate::rules::{Board, Dices, Player};
+use crate::{Action, Error, Game, GameResult};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes to base64 without padding, as used by GNU Backgammon IDs.
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let value = (buffer[0] as u32) << 16 | (buffer[1] as u32) << 8 | buffer[2] as u32;
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decode base64 without padding into the given amount of bytes.
+fn decode_base64(id: &str, len: usize) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(len);
+    let mut value: u32 = 0;
+    let mut bits = 0;
+    for c in id.bytes() {
+        let digit = BASE64.iter().position(|&b| b == c)? as u32;
+        value = value << 6 | digit;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((value >> bits) as u8);
+            value &= (1 << bits) - 1;
+        }
+    }
+    if bytes.len() == len {
+        Some(bytes)
+    } else {
+        None
+    }
+}
+
+impl Board {
+    /// Get the GNU Backgammon position ID of the board for the player on roll.
+    ///
+    /// The 80 bit key lists the checkers of the opponent first, followed by the checkers of the
+    /// player on roll, each from their own perspective with the bar last.
+    ///
+    /// ```
+    /// use backgammon::rules::{Board, Player};
+    ///
+    /// let board = Board::new();
+    /// assert_eq!(board.position_id(Player::Player0).unwrap(), "4HPwATDgc/ABMA");
+    /// ```
+    pub fn position_id(&self, player: Player) -> Result<String, Error> {
+        let opponent = match player {
+            Player::Player0 => Player::Player1,
+            Player::Player1 => Player::Player0,
+            Player::Nobody => return Err(Error::PlayerInvalid),
+        };
+
+        let mut key = [0u8; 10];
+        let mut bit = 0;
+        for p in [opponent, player] {
+            for checkers in self.checkers(p)? {
+                // checkers are written as ones, each field is terminated by a zero
+                for _ in 0..checkers {
+                    key[bit / 8] |= 1 << (bit % 8);
+                    bit += 1;
+                }
+                bit += 1;
+            }
+        }
+
+        Ok(encode_base64(&key))
+    }
+
+    /// Create a board from a GNU Backgammon position ID, given the player on roll.
+    pub fn from_position_id(id: &str, player: Player) -> Result<Self, Error> {
+        if id.len() != 14 {
+            return Err(Error::PositionIdInvalid);
+        }
+        let key = decode_base64(id, 10).ok_or(Error::PositionIdInvalid)?;
+
+        let mut checkers = [[0u8; 25]; 2];
+        let mut index = 0;
+        for bit in 0..80 {
+            if index >= 50 {
+                break;
+            }
+            if key[bit / 8] >> (bit % 8) & 1 == 1 {
+                let field = &mut checkers[index / 25][index % 25];
+                *field += 1;
+                if *field > 15 {
+                    return Err(Error::PositionIdInvalid);
+                }
+            } else {
+                index += 1;
+            }
+        }
+
+        let [opponent, on_roll] = checkers;
+        let board = match player {
+            Player::Player0 => Board::from_checkers(&on_roll, &opponent),
+            Player::Player1 => Board::from_checkers(&opponent, &on_roll),
+            Player::Nobody => return Err(Error::PlayerInvalid),
+        };
+        board.map_err(|_| Error::PositionIdInvalid)
+    }
+}
+
+/// Write the lowest bits of a value to the key, least significant bit first
+fn put_bits(key: &mut [u8], bit: &mut usize, value: u32, len: usize) {
+    for i in 0..len {
+        if value >> i & 1 == 1 {
+            key[*bit / 8] |= 1 << (*bit % 8);
+        }
+        *bit += 1;
+    }
+}
+
+/// Read a value from the key, least significant bit first
+fn get_bits(key: &[u8], bit: &mut usize, len: usize) -> u32 {
+    let mut value = 0;
+    for i in 0..len {
+        value |= ((key[*bit / 8] >> (*bit % 8) & 1) as u32) << i;
+        *bit += 1;
+    }
+    value
+}
+
+/// Index of a player in the match ID
+fn player_bit(player: Player) -> u32 {
+    match player {
+        Player::Player1 => 1,
+        _ => 0,
+    }
+}
+
+/// Player of an index in the match ID
+fn bit_player(bit: u32) -> Player {
+    match bit {
+        1 => Player::Player1,
+        _ => Player::Player0,
+    }
+}
+
+impl Game {
+    /// Get the GNU Backgammon match ID of the game.
+    ///
+    /// The match ID packs the cube, the player on roll, the Crawford flag, the game state, the
+    /// dices, the length of the match and the score into 12 characters. The board is encoded
+    /// separately, see [`Board::position_id`] and [`Game::gnubg_id`]. Returns an error if the cube,
+    /// the length of the match or the score are too large to be encoded.
+    ///
+    /// ```
+    /// use backgammon::Game;
+    ///
+    /// let game = Game::new();
+    /// assert_eq!(game.match_id().unwrap(), "MADgAAAAAAAA");
+    /// ```
+    pub fn match_id(&self) -> Result<String, Error> {
+        let cube = self.cube.value().trailing_zeros();
+        if cube > 15 {
+            return Err(Error::CubeValueInvalid);
+        }
+        // the length of the match and the score have 15 bits each
+        if [self.rules.points, self.score.0, self.score.1]
+            .iter()
+            .any(|&value| value >= 1 << 15)
+        {
+            return Err(Error::MatchIdInvalid);
+        }
+        let owner = match self.cube.owner() {
+            Player::Nobody => 3,
+            player => player_bit(player),
+        };
+        // games ended by a resignation or a dropped double have their own state
+        let (resigned, dropped) = match self.history().iter().rev().nth(1) {
+            Some(Action::Resign { result, .. }) => (Some(*result), false),
+            Some(Action::Drop { .. }) => (None, true),
+            _ => (None, false),
+        };
+        let (state, on_roll) = match (self.result, self.who_plays) {
+            (Some(_), _) if resigned.is_some() => (3, self.winner),
+            (Some(_), _) if dropped => (4, self.winner),
+            (Some(_), _) => (2, self.winner),
+            (None, Player::Nobody) => (0, Player::Player0),
+            (None, player) => (1, player),
+        };
+        let turn = if self.cube_received {
+            on_roll.other()
+        } else {
+            on_roll
+        };
+        let dices = if state == 1 && !self.roll_first {
+            self.dices.values
+        } else {
+            (0, 0)
+        };
+
+        let mut key = [0u8; 9];
+        let mut bit = 0;
+        put_bits(&mut key, &mut bit, cube, 4);
+        put_bits(&mut key, &mut bit, owner, 2);
+        put_bits(&mut key, &mut bit, player_bit(on_roll), 1);
+        put_bits(&mut key, &mut bit, self.crawford as u32, 1);
+        put_bits(&mut key, &mut bit, state, 3);
+        put_bits(&mut key, &mut bit, player_bit(turn), 1);
+        put_bits(&mut key, &mut bit, self.cube_received as u32, 1);
+        // resignation offers are not supported, a resigned game keeps the result resigned
+        let resignation = match resigned {
+            Some(GameResult::Single) => 1,
+            Some(GameResult::Gammon) => 2,
+            Some(GameResult::Backgammon) => 3,
+            None => 0,
+        };
+        put_bits(&mut key, &mut bit, resignation, 2);
+        put_bits(&mut key, &mut bit, dices.0 as u32, 3);
+        put_bits(&mut key, &mut bit, dices.1 as u32, 3);
+        put_bits(&mut key, &mut bit, self.rules.points, 15);
+        put_bits(&mut key, &mut bit, self.score.0, 15);
+        put_bits(&mut key, &mut bit, self.score.1, 15);
+
+        Ok(encode_base64(&key))
+    }
+
+    /// Create a game from a GNU Backgammon match ID.
+    ///
+    /// The board is not part of the match ID and is set to the starting position, see
+    /// [`Game::from_gnubg_id`] to import the board as well. A finished game is won by the player
+    /// on roll with a single game. A game ended by a dropped double is won at the value of the
+    /// cube before the double, a resigned game with the result resigned by the opponent.
+    pub fn from_match_id(id: &str) -> Result<Self, Error> {
+        if id.len() != 12 {
+            return Err(Error::MatchIdInvalid);
+        }
+        let key = decode_base64(id, 9).ok_or(Error::MatchIdInvalid)?;
+
+        let mut bit = 0;
+        let cube = get_bits(&key, &mut bit, 4);
+        let owner = get_bits(&key, &mut bit, 2);
+        let on_roll = bit_player(get_bits(&key, &mut bit, 1));
+        let crawford = get_bits(&key, &mut bit, 1) == 1;
+        let state = get_bits(&key, &mut bit, 3);
+        let turn = bit_player(get_bits(&key, &mut bit, 1));
+        let double_offered = get_bits(&key, &mut bit, 1) == 1;
+        let resignation = get_bits(&key, &mut bit, 2);
+        let dices = (
+            get_bits(&key, &mut bit, 3) as u8,
+            get_bits(&key, &mut bit, 3) as u8,
+        );
+        let points = get_bits(&key, &mut bit, 15);
+        let score = (get_bits(&key, &mut bit, 15), get_bits(&key, &mut bit, 15));
+
+        let rolled = (1..=6).contains(&dices.0) && (1..=6).contains(&dices.1);
+        if owner == 2
+            || state > 4
+            || !(rolled || dices == (0, 0))
+            || double_offered && (state != 1 || rolled || turn == on_roll)
+        {
+            return Err(Error::MatchIdInvalid);
+        }
+
+        let mut game = Game::new();
+        game.rules.points = points;
+        game.rules.crawford = game.rules.crawford || crawford;
+        game.crawford = crawford;
+        game.score = score;
+        game.cube.set(1 << cube)?;
+        game.cube.set_owner(match owner {
+            3 => Player::Nobody,
+            owner => bit_player(owner),
+        });
+
+        match state {
+            0 => {}
+            1 => {
+                game.who_plays = on_roll;
+                game.cube_received = double_offered;
+                if rolled {
+                    game.dices = Dices::from_values(dices);
+                } else {
+                    game.roll_first = true;
+                }
+            }
+            4 => {
+                game.who_plays = on_roll;
+                game.cube_received = true;
+                let _ = game.reject_double(on_roll.other())?;
+            }
+            3 => {
+                let result = match resignation {
+                    2 => GameResult::Gammon,
+                    3 => GameResult::Backgammon,
+                    _ => GameResult::Single,
+                };
+                let _ = game.resign(on_roll.other(), result)?;
+            }
+            _ => {
+                game.winner = on_roll;
+                game.result = Some(GameResult::Single);
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Get the GNU Backgammon ID of the game, which combines the position ID and the match ID
+    /// separated by a colon, e.g. `4HPwATDgc/ABMA:cAkAAAAAAAAA`.
+    pub fn gnubg_id(&self) -> Result<String, Error> {
+        let player = match (self.result, self.who_plays) {
+            (Some(_), _) => self.winner,
+            (None, Player::Nobody) => Player::Player0,
+            (None, player) => player,
+        };
+        Ok(format!(
+            "{}:{}",
+            self.board.position_id(player)?,
+            self.match_id()?
+        ))
+    }
+
+    /// Create a game from a GNU Backgammon ID, which combines the position ID and the match ID
+    /// separated by a colon.
+    pub fn from_gnubg_id(id: &str) -> Result<Self, Error> {
+        let (position_id, match_id) = id.split_once(':').ok_or(Error::MatchIdInvalid)?;
+        let mut game = Game::from_match_id(match_id)?;
+        let player = match game.result {
+            Some(_) => game.winner,
+            None => game.who_plays,
+        };
+        game.board = Board::from_position_id(
+            position_id,
+            match player {
+                Player::Nobody => Player::Player0,
+                player => player,
+            },
+        )?;
+        if game.result.is_some() && game.board.off(game.winner)? == 15 {
+            game.result = Some(game.board_result(game.winner)?);
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{CheckerMove, Field, Roll, ScriptedDices};
+
+    #[test]
+    fn base64() {
+        let bytes = [0xe0, 0x73, 0xf0, 0x01, 0x30, 0xe0, 0x73, 0xf0, 0x01, 0x30];
+        assert_eq!(encode_base64(&bytes), "4HPwATDgc/ABMA");
+        assert_eq!(decode_base64("4HPwATDgc/ABMA", 10), Some(bytes.to_vec()));
+        assert_eq!(decode_base64("4HPwATDgc/AB!A", 10), None);
+    }
+
+    #[test]
+    fn position_id_start() -> Result<(), Error> {
+        let board = Board::new();
+        assert_eq!(board.position_id(Player::Player0)?, "4HPwATDgc/ABMA");
+        assert_eq!(board.position_id(Player::Player1)?, "4HPwATDgc/ABMA");
+        assert_eq!(
+            Board::from_position_id("4HPwATDgc/ABMA", Player::Player0)?,
+            board
+        );
+        assert_eq!(
+            Board::from_position_id("4HPwATDgc/ABMA", Player::Player1)?,
+            board
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn position_id_after_opening() -> Result<(), Error> {
+        // Player0 opens with 31: 8/5 6/5, Player1 is on roll
+        let mut board = Board::new();
+        board.set(Player::Player0, 7, -1)?;
+        board.set(Player::Player0, 5, -1)?;
+        board.set(Player::Player0, 4, 2)?;
+
+        assert_eq!(board.position_id(Player::Player1)?, "sGfwATDgc/ABMA");
+        assert_eq!(
+            Board::from_position_id("sGfwATDgc/ABMA", Player::Player1)?,
+            board
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn position_id_round_trip() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set(Player::Player1, 23, -1)?;
+        board.set(Player::Player0, 23, -1)?;
+        board.set_bar(Player::Player1, 1)?;
+        board.set_off(Player::Player0, 1)?;
+        assert_eq!(board.checkers(Player::Player1)?[24], 1);
+
+        for player in [Player::Player0, Player::Player1] {
+            let id = board.position_id(player)?;
+            assert_eq!(id.len(), 14);
+            assert_eq!(Board::from_position_id(&id, player)?, board);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn position_id_invalid() {
+        assert!(Board::new().position_id(Player::Nobody).is_err());
+        for id in ["", "4HPwATDgc/ABM", "4HPwATDgc/ABMAA", "4HPwATDgc/AB*A"] {
+            assert!(matches!(
+                Board::from_position_id(id, Player::Player0),
+                Err(Error::PositionIdInvalid)
+            ));
+        }
+        // more than 15 checkers
+        assert!(matches!(
+            Board::from_position_id("//////////////", Player::Player0),
+            Err(Error::PositionIdInvalid)
+        ));
+    }
+
+    #[test]
+    fn match_id_example() -> Result<(), Error> {
+        // match to 9 points, score 2-4, Player1 has rolled 52, cube at 2 owned by Player0
+        let game = Game::from_match_id("QYkqASAAIAAA")?;
+        assert_eq!(game.rules.points, 9);
+        assert_eq!(game.score, (2, 4));
+        assert_eq!(game.cube.value(), 2);
+        assert_eq!(game.cube.owner(), Player::Player0);
+        assert_eq!(game.who_plays, Player::Player1);
+        assert_eq!(game.dices.values, (5, 2));
+        assert!(!game.crawford());
+        assert!(game.result.is_none());
+        assert_eq!(game.match_id()?, "QYkqASAAIAAA");
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_round_trip() -> Result<(), Error> {
+        let mut game = Game::new();
+        assert_eq!(game.match_id()?, "MADgAAAAAAAA");
+
+        game.rules.points = 7;
+        game.score = (6, 3);
+        game.set_crawford(true, false);
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let id = game.match_id()?;
+        let imported = Game::from_match_id(&id)?;
+        assert_eq!(imported.who_plays, Player::Player0);
+        assert_eq!(imported.dices.values, (3, 1));
+        assert!(imported.crawford());
+        assert_eq!(imported.score, (6, 3));
+        assert_eq!(imported.match_id()?, id);
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_double_offered() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 5;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.offer_double(Player::Player1)?;
+
+        let id = game.match_id()?;
+        let mut imported = Game::from_match_id(&id)?;
+        assert_eq!(imported.who_plays, Player::Player1);
+        assert_eq!(imported.match_id()?, id);
+        let _ = imported.accept_double(Player::Player0)?;
+        assert_eq!(imported.cube.value(), 2);
+        assert_eq!(imported.cube.owner(), Player::Player0);
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_game_ended() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 5;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.offer_double(Player::Player1)?;
+        let _ = game.reject_double(Player::Player0)?;
+
+        let id = game.match_id()?;
+        let imported = Game::from_match_id(&id)?;
+        assert_eq!(imported.winner, Player::Player1);
+        assert_eq!(imported.result, Some(GameResult::Single));
+        assert_eq!(imported.points(), 1);
+        assert_eq!(imported.match_id()?, id);
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_game_dropped() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 5;
+        game.cube.set(2)?;
+        game.cube.set_owner(Player::Player1);
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.offer_double(Player::Player1)?;
+        let _ = game.reject_double(Player::Player0)?;
+
+        // the game state is written in bits 8 to 10
+        let id = game.match_id()?;
+        let key = decode_base64(&id, 9).ok_or(Error::MatchIdInvalid)?;
+        assert_eq!(get_bits(&key, &mut 8, 3), 4);
+
+        let imported = Game::from_match_id(&id)?;
+        assert_eq!(imported.winner, Player::Player1);
+        assert_eq!(imported.result, Some(GameResult::Single));
+        assert_eq!(imported.cube.value(), 2);
+        assert_eq!(imported.points(), 2);
+        assert_eq!(imported.match_id()?, id);
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_game_resigned() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 5;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = game.resign(Player::Player1, GameResult::Gammon)?;
+
+        // the game state is written in bits 8 to 10, the resigned result in bits 13 and 14
+        let id = game.match_id()?;
+        let key = decode_base64(&id, 9).ok_or(Error::MatchIdInvalid)?;
+        assert_eq!(get_bits(&key, &mut 8, 3), 3);
+        assert_eq!(get_bits(&key, &mut 13, 2), 2);
+
+        let imported = Game::from_match_id(&id)?;
+        assert_eq!(imported.winner, Player::Player0);
+        assert_eq!(imported.result, Some(GameResult::Gammon));
+        assert_eq!(
+            imported.history()[0],
+            Action::Resign {
+                player: Player::Player1,
+                result: GameResult::Gammon
+            }
+        );
+        assert_eq!(imported.points(), 2);
+        assert_eq!(imported.match_id()?, id);
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_too_large() -> Result<(), Error> {
+        let mut game = Game::new();
+        game.rules.points = 0;
+        game.score = (32767, 12);
+        let imported = Game::from_match_id(&game.match_id()?)?;
+        assert_eq!(imported.score, (32767, 12));
+
+        game.score = (32768, 12);
+        assert!(matches!(game.match_id(), Err(Error::MatchIdInvalid)));
+        game.score = (0, 40000);
+        assert!(matches!(game.match_id(), Err(Error::MatchIdInvalid)));
+        game.score = (0, 0);
+        game.rules.points = 1 << 15;
+        assert!(matches!(game.match_id(), Err(Error::MatchIdInvalid)));
+        Ok(())
+    }
+
+    #[test]
+    fn match_id_invalid() {
+        for id in ["", "QYkqASAAIAA", "QYkqASAAIAAA:", "QYkqASAA*AAA"] {
+            assert!(matches!(
+                Game::from_match_id(id),
+                Err(Error::MatchIdInvalid)
+            ));
+        }
+        // game state 7 does not exist
+        assert!(matches!(
+            Game::from_match_id("AAcAAAAAAAAA"),
+            Err(Error::MatchIdInvalid)
+        ));
+    }
+
+    #[test]
+    fn gnubg_id() -> Result<(), Error> {
+        let game = Game::from_gnubg_id("4HPwATDgc/ABMA:cAkAAAAAAAAA")?;
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.who_plays, Player::Player1);
+        assert_eq!(game.cube.owner(), Player::Nobody);
+        assert!(game.rules.money_play());
+        assert_eq!(game.gnubg_id()?, "4HPwATDgc/ABMA:cAkAAAAAAAAA");
+
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let id = game.gnubg_id()?;
+        assert!(id.starts_with("sGfwATDgc/ABMA:"));
+        let imported = Game::from_gnubg_id(&id)?;
+        assert_eq!(imported.board, game.board);
+        assert_eq!(imported.who_plays, Player::Player1);
+
+        assert!(Game::from_gnubg_id("4HPwATDgc/ABMA").is_err());
+        Ok(())
+    }
+}
@@ -93,14 +93,31 @@
     variant_size_differences
 )] // be tough on code quality
 
+/// Implements the ASCII diagram of the board
+mod ascii;
 /// Implements all possible Backgammon errors
 mod error;
 pub use error::Error;
+/// Implements the FIBS board strings and client protocol
+pub mod fibs;
 /// Implements a Backgammon game
 mod game;
-pub use game::Game;
+pub use game::{Action, Game, GameResult};
+/// Implements the GNU Backgammon position and match IDs
+mod gnubg;
+/// Implements the Jellyfish and GNU Backgammon .mat match files
+mod mat;
 /// Implements a Backgammon match
 mod r#match;
 pub use r#match::Match;
+/// Implements the standard notation of Backgammon moves
+pub mod notation;
 /// Implements the board, the dices, the cube, and all other Backgammon rules
 pub mod rules;
+/// Implements the Smart Game Format (SGF) for Backgammon
+mod sgf;
+pub use sgf::SgfRecord;
+/// Implements the SVG rendering of boards and games
+pub mod svg;
+/// Implements the eXtreme Gammon XGID
+mod xgid;
@@ -0,0 +1,657 @@
+use crate::notation;
+use crate::rules::{CheckerMove, Player, Roll, ScriptedDices};
+use crate::{Action, Error, Game, GameResult, Match};
+
+/// Width of the column of Player0 in move lines
+const COLUMN: usize = 28;
+
+/// Represents an entry in a move line of a .mat file
+#[derive(Debug, PartialEq)]
+enum Entry {
+    /// dices rolled and the moves played with them
+    Turn((u8, u8), String),
+    /// double with the value of the cube offered
+    Double(u64),
+    /// double accepted
+    Take,
+    /// double rejected
+    Drop,
+    /// beaver with the value of the cube after it
+    Beaver(u64),
+    /// raccoon with the value of the cube after it
+    Raccoon(u64),
+    /// game won with the amount of points
+    Win(u64),
+}
+
+/// Name of a player, falling back to the label of the player if no name is set
+fn name(name: &str, player: Player) -> String {
+    if name.is_empty() {
+        player.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Convert the history of a game into the entries of each player, in order
+fn entries(game: &Game) -> Vec<(Player, String)> {
+    let mut entries = Vec::new();
+    let mut turn: Option<(Player, String, Vec<CheckerMove>)> = None;
+    // whether the last move of the turn hit a checker
+    let mut last_hit = false;
+    let mut opening = true;
+
+    for action in game.history() {
+        match action {
+            Action::Roll { player, dices } => {
+                // tied opening rolls can not be recorded
+                if *player == Player::Nobody {
+                    continue;
+                }
+                let dices = if opening {
+                    (dices.0.max(dices.1), dices.0.min(dices.1))
+                } else {
+                    *dices
+                };
+                opening = false;
+                turn = Some((*player, format!("{}{}:", dices.0, dices.1), Vec::new()));
+            }
+            Action::Move {
+                checker_move, hit, ..
+            } => {
+                if let Some((_, _, moves)) = turn.as_mut() {
+                    // a checker moving on without having hit is written as a single move
+                    match moves.last_mut() {
+                        Some(last) if !last_hit && last.to == checker_move.from => {
+                            *last = CheckerMove::new(last.from, checker_move.to);
+                        }
+                        _ => moves.push(*checker_move),
+                    }
+                    last_hit = *hit;
+                }
+            }
+            Action::Commit { .. } => {
+                if let Some((player, dices, moves)) = turn.take() {
+                    entries.push((player, turn_text(&dices, &moves)));
+                }
+            }
+            Action::Double { player, value } => {
+                entries.push((*player, format!(" Doubles => {}", value)));
+            }
+            Action::Take { player } => entries.push((*player, String::from(" Takes"))),
+            Action::Drop { player } => entries.push((*player, String::from(" Drops"))),
+            Action::Beaver { player, value } => {
+                entries.push((*player, format!(" Beavers => {}", value)));
+            }
+            Action::Raccoon { player, value } => {
+                entries.push((*player, format!(" Raccoons => {}", value)));
+            }
+            // the result is written after all entries
+            Action::MurphyDouble { .. } | Action::Resign { .. } | Action::GameEnd { .. } => {}
+        }
+    }
+
+    // the turn currently being played
+    if let Some((player, dices, moves)) = turn {
+        entries.push((player, turn_text(&dices, &moves)));
+    }
+    entries
+}
+
+/// Text of a turn, the dices followed by the moves if any
+fn turn_text(dices: &str, moves: &[CheckerMove]) -> String {
+    if moves.is_empty() {
+        dices.to_string()
+    } else {
+        format!("{} {}", dices, notation::format(moves))
+    }
+}
+
+/// Split a text into its whitespace separated tokens together with their offset in the text
+fn tokenize(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, &text[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, &text[s..]));
+    }
+    tokens
+}
+
+/// Parse the entries of a move line, or of a line announcing the winner, together with the offset
+/// each entry starts at
+fn parse_entries(text: &str) -> Result<Vec<(usize, Entry)>, String> {
+    let (offsets, tokens): (Vec<usize>, Vec<&str>) = tokenize(text).into_iter().unzip();
+    let mut entries = Vec::new();
+    let mut i = 0;
+
+    let value = |i: usize| -> Result<u64, String> {
+        match (tokens.get(i).copied(), tokens.get(i + 1)) {
+            (Some("=>"), Some(value)) => value
+                .parse()
+                .map_err(|_| format!("invalid cube value '{}'", value)),
+            _ => Err(String::from("missing cube value")),
+        }
+    };
+
+    while i < tokens.len() {
+        let (offset, token) = (offsets[i], tokens[i]);
+        i += 1;
+        match token {
+            "Doubles" => {
+                entries.push((offset, Entry::Double(value(i)?)));
+                i += 2;
+            }
+            "Beavers" => {
+                entries.push((offset, Entry::Beaver(value(i)?)));
+                i += 2;
+            }
+            "Raccoons" => {
+                entries.push((offset, Entry::Raccoon(value(i)?)));
+                i += 2;
+            }
+            "Takes" => entries.push((offset, Entry::Take)),
+            "Drops" => entries.push((offset, Entry::Drop)),
+            "Wins" => {
+                let points = tokens
+                    .get(i)
+                    .and_then(|p| p.parse().ok())
+                    .ok_or_else(|| String::from("missing points"))?;
+                entries.push((offset, Entry::Win(points)));
+                // skip "point(s)" and "and the match"
+                i = tokens.len();
+            }
+            _ => {
+                let dices = parse_dices(token).ok_or_else(|| format!("unexpected '{}'", token))?;
+                let mut moves = Vec::new();
+                while i < tokens.len() && !is_keyword(tokens[i]) && parse_dices(tokens[i]).is_none()
+                {
+                    moves.push(tokens[i]);
+                    i += 1;
+                }
+                entries.push((offset, Entry::Turn(dices, moves.join(" "))));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Parse dices written as two digits followed by a colon, e.g. `31:`
+fn parse_dices(token: &str) -> Option<(u8, u8)> {
+    let digits = token.strip_suffix(':')?.as_bytes();
+    match digits {
+        [d0 @ b'1'..=b'6', d1 @ b'1'..=b'6'] => Some((d0 - b'0', d1 - b'0')),
+        _ => None,
+    }
+}
+
+/// Check if a token starts an entry other than a turn
+fn is_keyword(token: &str) -> bool {
+    matches!(
+        token,
+        "Doubles" | "Takes" | "Drops" | "Beavers" | "Raccoons" | "Wins"
+    )
+}
+
+/// Parse the line with the names and scores of both players, e.g. ` Alice : 0   Bob : 2`
+fn parse_players(line: &str) -> Option<(String, u32, String, u32)> {
+    let parts: Vec<&str> = line.split(" : ").collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let (score0, name1) = parts[1].trim().split_once(char::is_whitespace)?;
+    Some((
+        parts[0].trim().to_string(),
+        score0.parse().ok()?,
+        name1.trim().to_string(),
+        parts[2].trim().parse().ok()?,
+    ))
+}
+
+/// Returns the player who has to write an entry, or nobody if any player can write it, as for the
+/// opening roll
+fn actor(game: &Game, entry: &Entry) -> Player {
+    match entry {
+        Entry::Turn(..) | Entry::Double(_) | Entry::Raccoon(_) => game.who_plays,
+        Entry::Take | Entry::Drop | Entry::Beaver(_) => game.who_plays.other(),
+        Entry::Win(_) => game.winner,
+    }
+}
+
+/// Resign the game for the given player, conceding the result worth the points won by the
+/// opponent
+fn resign(game: &mut Game, player: Player, points: u64) -> Result<(), Error> {
+    for result in [
+        GameResult::Single,
+        GameResult::Gammon,
+        GameResult::Backgammon,
+    ] {
+        let mut resigned = game.clone();
+        let _ = resigned.resign(player, result)?;
+        if resigned.points() == points {
+            *game = resigned;
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Replay an entry of a .mat file on the game
+fn replay(game: &mut Game, entry: Entry, opening: Player) -> Result<(), Error> {
+    match entry {
+        Entry::Turn(dices, moves) => {
+            let (player, dices) = match game.who_plays {
+                // the player who opens the game plays the larger dice
+                Player::Nobody => {
+                    if dices.0 == dices.1 {
+                        return Err(Error::DiceInvalid);
+                    }
+                    let (high, low) = (dices.0.max(dices.1), dices.0.min(dices.1));
+                    match opening {
+                        Player::Player1 => (opening, (low, high)),
+                        _ => (Player::Player0, (high, low)),
+                    }
+                }
+                player => (player, dices),
+            };
+            let _ = game.roll_with(&mut ScriptedDices::new(vec![dices]))?;
+            if game.who_plays != player {
+                return Err(Error::NotYourTurn);
+            }
+            let moves = if moves.is_empty() {
+                Vec::new()
+            } else {
+                notation::parse(&moves)?
+            };
+            let _ = game.play(player, &moves)?;
+        }
+        Entry::Double(value) => {
+            let _ = game.offer_double(game.who_plays)?;
+            if game.cube.offer(game.who_plays.other())? != value {
+                return Err(Error::CubeValueInvalid);
+            }
+        }
+        Entry::Take => {
+            let _ = game.accept_double(game.who_plays.other())?;
+        }
+        Entry::Drop => {
+            let _ = game.reject_double(game.who_plays.other())?;
+        }
+        Entry::Beaver(value) => {
+            let _ = game.beaver(game.who_plays.other())?;
+            if game.cube.value() != value {
+                return Err(Error::CubeValueInvalid);
+            }
+        }
+        Entry::Raccoon(value) => {
+            let _ = game.raccoon(game.who_plays)?;
+            if game.cube.value() != value {
+                return Err(Error::CubeValueInvalid);
+            }
+        }
+        // the points won are checked by the caller
+        Entry::Win(_) => {}
+    }
+    Ok(())
+}
+
+impl Match {
+    /// Export the match to the .mat format of Jellyfish and GNU Backgammon.
+    ///
+    /// Every game lists the rolls and moves of both players, Player0 on the left and Player1 on
+    /// the right, together with all cube actions and the points won. Tied opening rolls are not
+    /// part of the format and are left out.
+    pub fn to_mat(&self) -> Result<String, Error> {
+        let names = (
+            name(&self.players.0, Player::Player0),
+            name(&self.players.1, Player::Player1),
+        );
+        let mut mat = format!(" {} point match\n", self.rules.points);
+
+        for (i, game) in self.games.iter().enumerate() {
+            mat.push_str(&format!("\n Game {}\n", i + 1));
+            let player0 = format!("{} : {}", names.0, game.score.0);
+            mat.push_str(&format!(
+                " {:<w$}{} : {}\n",
+                player0,
+                names.1,
+                game.score.1,
+                w = COLUMN + 4
+            ));
+
+            let mut lines: Vec<(String, String)> = Vec::new();
+            for (player, text) in entries(game) {
+                match (player, lines.last_mut()) {
+                    (Player::Player1, Some(line)) if line.1.is_empty() => line.1 = text,
+                    (Player::Player1, _) => lines.push((String::new(), text)),
+                    _ => lines.push((text, String::new())),
+                }
+            }
+            for (n, (left, right)) in lines.iter().enumerate() {
+                let line = format!("{:>3}) {:<w$}{}", n + 1, left, right, w = COLUMN);
+                mat.push_str(line.trim_end());
+                mat.push('\n');
+            }
+
+            if game.result.is_some() {
+                let points = game.points();
+                let indent = match game.winner {
+                    Player::Player1 => COLUMN + 5,
+                    _ => 5,
+                };
+                mat.push_str(&format!(
+                    "{:indent$} Wins {} point{}",
+                    "",
+                    points,
+                    if points == 1 { "" } else { "s" },
+                    indent = indent
+                ));
+                if i + 1 == self.games.len() && self.winner() != Player::Nobody {
+                    mat.push_str(" and the match");
+                }
+                mat.push('\n');
+            }
+        }
+
+        Ok(mat)
+    }
+
+    /// Import a match from the .mat format of Jellyfish and GNU Backgammon.
+    ///
+    /// Each roll, move and cube action is replayed through the engine, so that any illegal action
+    /// in the transcript is reported as an error together with the line it occurs in, as is any
+    /// entry written in the column of the other player. A game still running when its winner is
+    /// announced has been resigned for the points won.
+    pub fn from_mat(mat: &str) -> Result<Self, Error> {
+        let mut m = Match::new();
+        let mut players = false;
+
+        for (n, line) in mat.lines().enumerate() {
+            let invalid = |reason: &str| Error::MatInvalid(format!("line {}: {}", n + 1, reason));
+            let text = line.trim();
+
+            if text.is_empty() || text.starts_with(';') {
+                continue;
+            }
+            if let Some(points) = text.strip_suffix("point match") {
+                m.rules.points = points
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("invalid length"))?;
+                continue;
+            }
+            if text.starts_with("Game ") {
+                let _ = m.new_game().map_err(|e| invalid(&e.to_string()))?;
+                players = true;
+                continue;
+            }
+
+            let game = m
+                .games
+                .last_mut()
+                .ok_or_else(|| invalid("no game started"))?;
+
+            if players {
+                let (name0, score0, name1, score1) =
+                    parse_players(text).ok_or_else(|| invalid("invalid players"))?;
+                if (score0, score1) != game.score {
+                    return Err(invalid("score does not match the games played"));
+                }
+                m.players = (name0, name1);
+                players = false;
+                continue;
+            }
+
+            // entries starting in the right half belong to Player1, the lines announcing the
+            // winner are not numbered
+            let (content, split) = match text.split_once(')') {
+                Some((number, content)) if number.parse::<u32>().is_ok() => (content, COLUMN / 2),
+                _ => (line, COLUMN / 2 + 4),
+            };
+
+            for (offset, entry) in parse_entries(content).map_err(|e| invalid(&e))? {
+                let column = if offset > split {
+                    Player::Player1
+                } else {
+                    Player::Player0
+                };
+                // the resignation is only recorded in the points won
+                if let Entry::Win(points) = entry {
+                    if game.result.is_none() {
+                        resign(game, column.other(), points)
+                            .map_err(|e| invalid(&e.to_string()))?;
+                    }
+                }
+                let actor = actor(game, &entry);
+                if actor != Player::Nobody && actor != column {
+                    return Err(invalid(&format!("entry of {} in the wrong column", actor)));
+                }
+                if let Entry::Win(points) = entry {
+                    if game.result.is_none() || game.points() != points {
+                        return Err(invalid("points won do not match the game"));
+                    }
+                }
+                // the player opening the game is given by the column of the first entry
+                replay(game, entry, column).map_err(|e| invalid(&e.to_string()))?;
+            }
+        }
+
+        Ok(m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Field, MatchRules};
+
+    /// Play a short match: Player0 wins the first game by a dropped double, Player1 wins the
+    /// second game by a dropped redouble.
+    fn short_match() -> Result<Match, Error> {
+        let mut m = Match::new().with_points(3);
+        m.players = (String::from("Alice"), String::from("Bob"));
+
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1), (5, 2)]))?;
+        let _ = g.play(Player::Player0, &notation::parse("8/5 6/5")?)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(5, 2)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("13/8 13/11")?)?;
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.reject_double(Player::Player1)?;
+
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(4, 6)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("24/14")?)?;
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.accept_double(Player::Player1)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(6, 6)]))?;
+        let _ = g.play(Player::Player0, &notation::parse("24/18(2) 13/7(2)")?)?;
+        let _ = g.offer_double(Player::Player1)?;
+        let _ = g.reject_double(Player::Player0)?;
+        Ok(m)
+    }
+
+    const SHORT_MATCH: &str = " 3 point match
+
+ Game 1
+ Alice : 0                       Bob : 0
+  1) 31: 8/5 6/5                 52: 13/8 13/11
+  2)  Doubles => 2                Drops
+      Wins 1 point
+
+ Game 2
+ Alice : 1                       Bob : 0
+  1)                             64: 24/14
+  2)  Doubles => 2                Takes
+  3) 66: 24/18(2) 13/7(2)         Doubles => 4
+  4)  Drops
+                                  Wins 2 points
+";
+
+    #[test]
+    fn to_mat() -> Result<(), Error> {
+        let mut m = short_match()?;
+        assert_eq!(m.to_mat()?, SHORT_MATCH);
+
+        m.rules.points = 2;
+        assert!(m.to_mat()?.ends_with("Wins 2 points and the match\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn entries_compound() -> Result<(), Error> {
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(6, 5)]))?;
+        let _ = game.play(Player::Player0, &notation::parse("24/18/13")?)?;
+        assert_eq!(
+            entries(&game),
+            vec![(Player::Player0, String::from("65: 24/13"))]
+        );
+
+        // a checker which hits on its way keeps the intermediate point
+        let mut game = Game::new();
+        game.board.set(Player::Player1, 6, 1)?;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(6, 5)]))?;
+        let _ = game.play(Player::Player0, &notation::parse("24/18/13")?)?;
+        assert_eq!(
+            entries(&game),
+            vec![(Player::Player0, String::from("65: 24/18/13"))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_mat() -> Result<(), Error> {
+        let original = short_match()?;
+        let m = Match::from_mat(SHORT_MATCH)?;
+        assert_eq!(m.rules.points, 3);
+        assert_eq!(m.players, original.players);
+        assert_eq!(m.score(), (1, 2));
+        assert_eq!(m.games.len(), 2);
+        for (game, original) in m.games.iter().zip(&original.games) {
+            assert_eq!(game.history(), original.history());
+            assert_eq!(game.board, original.board);
+            assert_eq!(game.winner, original.winner);
+        }
+        assert_eq!(m.to_mat()?, SHORT_MATCH);
+        Ok(())
+    }
+
+    #[test]
+    fn from_mat_resigned() -> Result<(), Error> {
+        let mut m = Match::new().with_points(5);
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = g.play(Player::Player0, &notation::parse("8/5 6/5")?)?;
+        let _ = g.resign(Player::Player1, GameResult::Single)?;
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(4, 6)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("24/14")?)?;
+        let _ = g.resign(Player::Player0, GameResult::Gammon)?;
+
+        let mat = m.to_mat()?;
+        let imported = Match::from_mat(&mat)?;
+        assert_eq!(imported.score(), (1, 2));
+        for (game, original) in imported.games.iter().zip(&m.games) {
+            assert_eq!(game.history(), original.history());
+        }
+        assert_eq!(imported.to_mat()?, mat);
+        Ok(())
+    }
+
+    #[test]
+    fn from_mat_unfinished() -> Result<(), Error> {
+        let mut m = Match::new().with_points(1);
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = g.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(7), Field::Point(4)),
+                CheckerMove::new(Field::Point(5), Field::Point(4)),
+            ],
+        )?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 2)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("13/11(2) 6/4(2)")?)?;
+        let mat = m.to_mat()?;
+        assert!(mat.ends_with("  1) 31: 8/5 6/5                 22: 13/11(2) 6/4(2)\n"));
+        let imported = Match::from_mat(&mat)?;
+        assert_eq!(imported.games[0].history(), m.games[0].history());
+        Ok(())
+    }
+
+    #[test]
+    fn from_mat_invalid() {
+        let illegal = SHORT_MATCH.replace("31: 8/5 6/5", "31: 8/4 6/5");
+        assert!(matches!(
+            Match::from_mat(&illegal),
+            Err(Error::MatInvalid(reason)) if reason.starts_with("line 5:")
+        ));
+
+        let wrong_points = SHORT_MATCH.replace("Wins 2 points", "Wins 4 points");
+        assert!(matches!(
+            Match::from_mat(&wrong_points),
+            Err(Error::MatInvalid(_))
+        ));
+
+        let no_game = " 3 point match\n  1) 31: 8/5 6/5\n";
+        assert!(matches!(
+            Match::from_mat(no_game),
+            Err(Error::MatInvalid(reason)) if reason == "line 2: no game started"
+        ));
+
+        let garbage = SHORT_MATCH.replace("Takes", "Accepts");
+        assert!(Match::from_mat(&garbage).is_err());
+
+        // the take written in the column of the doubler
+        let wrong_column = SHORT_MATCH.replace(
+            "  2)  Doubles => 2                Takes",
+            "  2)  Doubles => 2\n  3)  Takes",
+        );
+        assert!(matches!(
+            Match::from_mat(&wrong_column),
+            Err(Error::MatInvalid(reason)) if reason == "line 13: entry of Player 1 in the wrong column"
+        ));
+
+        let wrong_winner = SHORT_MATCH.replace(
+            "                                  Wins 2 points",
+            "      Wins 2 points",
+        );
+        assert!(matches!(
+            Match::from_mat(&wrong_winner),
+            Err(Error::MatInvalid(reason)) if reason.starts_with("line 15:")
+        ));
+    }
+
+    #[test]
+    fn parse_entries_line() {
+        assert_eq!(
+            parse_entries(" 31: 8/5 6/5       Doubles => 2"),
+            Ok(vec![
+                (1, Entry::Turn((3, 1), String::from("8/5 6/5"))),
+                (19, Entry::Double(2))
+            ])
+        );
+        assert_eq!(
+            parse_entries(" 52:    Drops"),
+            Ok(vec![
+                (1, Entry::Turn((5, 2), String::new())),
+                (8, Entry::Drop)
+            ])
+        );
+        assert_eq!(
+            parse_entries("Wins 2 points and the match"),
+            Ok(vec![(0, Entry::Win(2))])
+        );
+        assert!(parse_entries("Doubles 2").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::game::Game;
-use crate::rules::{GameRules, MatchRules, Rules};
+use crate::rules::{GameRules, MatchRules, Player, Rules};
+use crate::Error;
 
 use std::fmt;
 
@@ -10,6 +11,8 @@ pub struct Match {
     pub rules: Rules,
     /// The games played in the match
     pub games: Vec<Game>,
+    /// The names of both players, used when exporting the match
+    pub players: (String, String),
 }
 
 // implement Display trait
@@ -37,6 +40,76 @@ impl Match {
     pub fn new() -> Self {
         Match::default()
     }
+
+    /// Returns the score of both players, summing up the points of all finished games
+    pub fn score(&self) -> (u32, u32) {
+        self.games
+            .iter()
+            .fold((0, 0), |score, game| match game.winner {
+                Player::Player0 => (score.0 + game.points() as u32, score.1),
+                Player::Player1 => (score.0, score.1 + game.points() as u32),
+                Player::Nobody => score,
+            })
+    }
+
+    /// Returns the winner of the match, i.e. the player who reached the points of the match first.
+    /// Money sessions, where the points are set to 0, never have a winner.
+    pub fn winner(&self) -> Player {
+        if self.rules.money_play() {
+            return Player::Nobody;
+        }
+
+        let score = self.score();
+        if score.0 >= self.rules.points {
+            Player::Player0
+        } else if score.1 >= self.rules.points {
+            Player::Player1
+        } else {
+            Player::Nobody
+        }
+    }
+
+    /// Start a new game with the rules of the match and return it:
+    /// ```
+    /// use backgammon::Match;
+    /// use backgammon::rules::{MatchRules, Roll};
+    ///
+    /// let mut m = Match::new().with_points(5);
+    /// let g = m.new_game()?;
+    /// g.roll()?;
+    /// # Ok::<(), backgammon::Error>(())
+    /// ```
+    ///
+    /// The previous game has to be finished before a new one can be started, and no more games can
+    /// be started once the match has a winner. For a money session without point target, set the
+    /// points to 0.
+    ///
+    /// If the Crawford rule is active, the game immediately after one player first reaches a
+    /// score of one point short of winning the match is the Crawford game, where no doubling is
+    /// allowed. All following games are post-Crawford games, where the Holland rule applies.
+    pub fn new_game(&mut self) -> Result<&mut Game, Error> {
+        if self.winner() != Player::Nobody {
+            return Err(Error::MatchEnded);
+        }
+        if self.games.last().is_some_and(|g| g.result.is_none()) {
+            return Err(Error::GameStarted);
+        }
+
+        let crawford_played = self.games.iter().any(|g| g.crawford());
+        let score = self.score();
+        let crawford = self.rules.crawford
+            && self.rules.points > 0
+            && !crawford_played
+            && (score.0 + 1 == self.rules.points || score.1 + 1 == self.rules.points);
+        let post_crawford = self.rules.crawford && crawford_played;
+
+        let mut game = Game::new();
+        game.rules = self.rules;
+        game.score = score;
+        game.set_crawford(crawford, post_crawford);
+        self.games.push(game);
+        Ok(self.games.last_mut().expect("game has just been added"))
+    }
 }
 
 /// Implements SetRules for Match
@@ -84,6 +157,7 @@ impl GameRules for Match {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::GameResult;
 
     #[test]
     fn test_default_match() {
@@ -134,4 +208,110 @@ mod tests {
             "Match with rules: Rules { points: 7, beaver: false, raccoon: false, murphy: false, murphy_limit: 0, jacoby: false, crawford: true, holland: false } and Games: []"
         );
     }
+
+    /// Let a player win the current game with a single game worth the given cube value
+    fn win(m: &mut Match, winner: Player, cube: u64) -> Result<(), Error> {
+        let last = m.games.len() - 1;
+        let g = &mut m.games[last];
+        g.cube.set(cube)?;
+        g.winner = winner;
+        g.result = Some(GameResult::Single);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score() -> Result<(), Error> {
+        let mut m = Match::new().with_points(5);
+        assert_eq!(m.score(), (0, 0));
+        let _ = m.new_game()?;
+        assert_eq!(m.score(), (0, 0));
+        win(&mut m, Player::Player1, 2)?;
+        let _ = m.new_game()?;
+        win(&mut m, Player::Player0, 1)?;
+        assert_eq!(m.score(), (1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_game_rules() -> Result<(), Error> {
+        let mut m = Match::new().with_points(5).with_jacoby();
+        let rules = m.rules;
+        let g = m.new_game()?;
+        assert_eq!(g.rules, rules);
+        assert!(!g.crawford());
+        assert!(!g.post_crawford());
+        Ok(())
+    }
+
+    #[test]
+    fn test_crawford() -> Result<(), Error> {
+        let mut m = Match::new().with_points(5);
+        let _ = m.new_game()?;
+        win(&mut m, Player::Player0, 4)?;
+        let g = m.new_game()?;
+        assert!(g.crawford());
+        assert!(!g.post_crawford());
+        assert_eq!(g.score, (4, 0));
+        win(&mut m, Player::Player1, 1)?;
+        let g = m.new_game()?;
+        assert_eq!(g.score, (4, 1));
+        assert!(!g.crawford());
+        assert!(g.post_crawford());
+        win(&mut m, Player::Player1, 2)?;
+        let g = m.new_game()?;
+        assert!(!g.crawford());
+        assert!(g.post_crawford());
+        Ok(())
+    }
+
+    #[test]
+    fn test_crawford_inactive() -> Result<(), Error> {
+        let mut m = Match::new().with_points(5);
+        m.rules.crawford = false;
+        let _ = m.new_game()?;
+        win(&mut m, Player::Player0, 4)?;
+        let g = m.new_game()?;
+        assert!(!g.crawford());
+        assert!(!g.post_crawford());
+        Ok(())
+    }
+
+    #[test]
+    fn test_winner() -> Result<(), Error> {
+        let mut m = Match::new().with_points(3);
+        let _ = m.new_game()?;
+        assert_eq!(m.winner(), Player::Nobody);
+        win(&mut m, Player::Player1, 2)?;
+        assert_eq!(m.winner(), Player::Nobody);
+        let _ = m.new_game()?;
+        win(&mut m, Player::Player1, 1)?;
+        assert_eq!(m.score(), (0, 3));
+        assert_eq!(m.winner(), Player::Player1);
+        assert!(matches!(m.new_game(), Err(Error::MatchEnded)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_game_running() -> Result<(), Error> {
+        let mut m = Match::new();
+        let _ = m.new_game()?;
+        assert!(matches!(m.new_game(), Err(Error::GameStarted)));
+        win(&mut m, Player::Player0, 1)?;
+        assert!(m.new_game().is_ok());
+        assert_eq!(m.games.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_money_session() -> Result<(), Error> {
+        let mut m = Match::new().with_points(0);
+        for _i in 0..3 {
+            let g = m.new_game()?;
+            assert!(!g.crawford());
+            win(&mut m, Player::Player0, 64)?;
+        }
+        assert_eq!(m.score(), (192, 0));
+        assert_eq!(m.winner(), Player::Nobody);
+        Ok(())
+    }
 }
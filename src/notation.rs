@@ -0,0 +1,335 @@
+//! # Standard Backgammon Move Notation
+//!
+//! Converts between the checker moves of the engine and the standard notation used in books,
+//! match files and analysis programs. Points are numbered from 1 to 24 from the perspective of the
+//! moving player, i.e. field 0 of [`Board::set`](crate::rules::Board::set) is point 1. The
+//! notation supports entering from the bar (`bar/20`), bearing off (`6/off`), hits (`13/7*`),
+//! moves of several checkers (`13/11(2)`) and compound moves of one checker (`24/18/13`):
+//! ```
+//! use backgammon::notation;
+//! use backgammon::rules::{CheckerMove, Field};
+//!
+//! let moves = notation::parse("24/18 13/11(2)")?;
+//! assert_eq!(moves[0], CheckerMove::new(Field::Point(23), Field::Point(17)));
+//! assert_eq!(notation::format(&moves), "24/18 13/11(2)");
+//! # Ok::<(), backgammon::Error>(())
+//! ```
+use crate::rules::{Board, CheckerMove, Field, Player};
+use crate::Error;
+
+/// Parse a play in standard notation into a list of checker moves.
+///
+/// Moves are separated by whitespace. Hit markers are accepted but not required, as hits follow
+/// from the position. Compound moves are split into one move per step.
+pub fn parse(s: &str) -> Result<Vec<CheckerMove>, Error> {
+    let mut moves = Vec::new();
+    for token in s.split_whitespace() {
+        moves.extend(parse_token(token)?);
+    }
+    Ok(moves)
+}
+
+/// Parse a single move like `13/11(2)` or `24/18*/13`
+fn parse_token(token: &str) -> Result<Vec<CheckerMove>, Error> {
+    let invalid = |reason: &str| Error::NotationInvalid(format!("{}: {}", token, reason));
+
+    // split off the multiplicity
+    let (path, count) = match token.strip_suffix(')') {
+        Some(rest) => {
+            let (path, count) = rest
+                .split_once('(')
+                .ok_or_else(|| invalid("unbalanced parenthesis"))?;
+            let count: usize = count.parse().map_err(|_| invalid("invalid multiplicity"))?;
+            if !(1..=4).contains(&count) {
+                return Err(invalid("multiplicity must be between 1 and 4"));
+            }
+            (path, count)
+        }
+        None => (token, 1),
+    };
+
+    let fields = path
+        .split('/')
+        .map(|f| parse_field(f.strip_suffix('*').unwrap_or(f)).map_err(&invalid))
+        .collect::<Result<Vec<Field>, Error>>()?;
+    if fields.len() < 2 {
+        return Err(invalid("a move needs a start and an end"));
+    }
+
+    let mut steps = Vec::new();
+    for pair in fields.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if from == Field::Off {
+            return Err(invalid("a checker can not move from off"));
+        }
+        if to == Field::Bar {
+            return Err(invalid("a checker can not move to the bar"));
+        }
+        if pips(from) <= pips(to) {
+            return Err(invalid("a checker has to move towards the home board"));
+        }
+        steps.push(CheckerMove::new(from, to));
+    }
+
+    let mut moves = Vec::new();
+    for _i in 0..count {
+        moves.extend(steps.iter().copied());
+    }
+    Ok(moves)
+}
+
+/// Parse a single field: `bar`, `off` or a point between 1 and 24
+fn parse_field(s: &str) -> Result<Field, &'static str> {
+    match s.to_ascii_lowercase().as_str() {
+        "bar" | "b" => Ok(Field::Bar),
+        "off" | "o" => Ok(Field::Off),
+        _ => match s.parse::<usize>() {
+            Ok(point) if (1..=24).contains(&point) => Ok(Field::Point(point - 1)),
+            Ok(25) => Ok(Field::Bar),
+            Ok(0) => Ok(Field::Off),
+            _ => Err("invalid point"),
+        },
+    }
+}
+
+/// Returns the pips a checker on a field still has to move
+fn pips(field: Field) -> usize {
+    match field {
+        Field::Bar => 25,
+        Field::Point(field) => field + 1,
+        Field::Off => 0,
+    }
+}
+
+/// Format a list of checker moves in standard notation, without hit markers. Consecutive moves of
+/// the same checker are joined, e.g. `24/18/13`, and consecutive identical moves are combined with
+/// their multiplicity, e.g. `13/11(2)`.
+pub fn format(moves: &[CheckerMove]) -> String {
+    combine(moves.iter().map(|&m| (m, false)))
+}
+
+/// Format the play of a player on a board in standard notation, marking hits with `*`.
+///
+/// The board is the position before the play. Returns an error if a move can not be applied to
+/// the board.
+pub fn format_with_hits(
+    board: &Board,
+    player: Player,
+    moves: &[CheckerMove],
+) -> Result<String, Error> {
+    let mut board = board.clone();
+    let mut steps = Vec::new();
+    for &m in moves {
+        let bar = board.get().bar;
+        board.apply_move(player, m)?;
+        let hit = match player {
+            Player::Player0 => board.get().bar.1 > bar.1,
+            _ => board.get().bar.0 > bar.0,
+        };
+        steps.push((m, hit));
+    }
+    Ok(combine(steps))
+}
+
+/// Join moves and their hit markers, chaining consecutive moves of the same checker and combining
+/// consecutive identical chains with their multiplicity
+fn combine(steps: impl IntoIterator<Item = (CheckerMove, bool)>) -> String {
+    let mut chains: Vec<(String, Field)> = Vec::new();
+    for (m, hit) in steps {
+        let to = format!("{}{}", m.to, if hit { "*" } else { "" });
+        match chains.last_mut() {
+            Some((token, end)) if *end == m.from => {
+                token.push('/');
+                token.push_str(&to);
+                *end = m.to;
+            }
+            _ => chains.push((format!("{}/{}", m.from, to), m.to)),
+        }
+    }
+
+    let mut counted: Vec<(String, usize)> = Vec::new();
+    for (token, _) in chains {
+        match counted.last_mut() {
+            Some((t, count)) if *t == token => *count += 1,
+            _ => counted.push((token, 1)),
+        }
+    }
+
+    counted
+        .into_iter()
+        .map(|(token, count)| {
+            if count > 1 {
+                format!("{}({})", token, count)
+            } else {
+                token
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(from: Field, to: Field) -> CheckerMove {
+        CheckerMove::new(from, to)
+    }
+
+    #[test]
+    fn test_parse_simple() -> Result<(), Error> {
+        assert_eq!(
+            parse("24/18 13/11")?,
+            vec![
+                m(Field::Point(23), Field::Point(17)),
+                m(Field::Point(12), Field::Point(10))
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bar_off() -> Result<(), Error> {
+        assert_eq!(
+            parse("bar/20 6/off")?,
+            vec![
+                m(Field::Bar, Field::Point(19)),
+                m(Field::Point(5), Field::Off)
+            ]
+        );
+        assert_eq!(parse("25/20 6/0")?, parse("Bar/20 6/Off")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_hits() -> Result<(), Error> {
+        assert_eq!(parse("13/7*")?, vec![m(Field::Point(12), Field::Point(6))]);
+        assert_eq!(
+            parse("bar/22* 6/2*(2)")?,
+            vec![
+                m(Field::Bar, Field::Point(21)),
+                m(Field::Point(5), Field::Point(1)),
+                m(Field::Point(5), Field::Point(1))
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_multiplicity() -> Result<(), Error> {
+        assert_eq!(
+            parse("13/11(2) 6/4(2)")?,
+            vec![
+                m(Field::Point(12), Field::Point(10)),
+                m(Field::Point(12), Field::Point(10)),
+                m(Field::Point(5), Field::Point(3)),
+                m(Field::Point(5), Field::Point(3))
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_compound() -> Result<(), Error> {
+        assert_eq!(
+            parse("24/18*/13")?,
+            vec![
+                m(Field::Point(23), Field::Point(17)),
+                m(Field::Point(17), Field::Point(12))
+            ]
+        );
+        assert_eq!(parse("24/21/18(2)")?.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for s in [
+            "24", "24/", "24/26", "0/5", "13/14", "off/5", "5/bar", "13/11(5)", "13/11(2",
+            "13/11(x)", "a/b",
+        ] {
+            assert!(
+                matches!(parse(s), Err(Error::NotationInvalid(_))),
+                "{} should be invalid",
+                s
+            );
+        }
+        assert!(parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            format(&[
+                m(Field::Point(12), Field::Point(10)),
+                m(Field::Point(12), Field::Point(10)),
+                m(Field::Bar, Field::Point(19)),
+                m(Field::Point(5), Field::Off)
+            ]),
+            "13/11(2) bar/20 6/off"
+        );
+        // identical moves which are not consecutive keep their order
+        assert_eq!(
+            format(&[
+                m(Field::Point(12), Field::Point(10)),
+                m(Field::Bar, Field::Point(19)),
+                m(Field::Point(12), Field::Point(10)),
+            ]),
+            "13/11 bar/20 13/11"
+        );
+        assert_eq!(format(&[]), "");
+    }
+
+    #[test]
+    fn test_format_with_hits() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set(Player::Player1, 17, 1)?;
+        // player 1's checker on its field 17 is on player 0's field 6
+        assert_eq!(
+            format_with_hits(
+                &board,
+                Player::Player0,
+                &[
+                    m(Field::Point(12), Field::Point(6)),
+                    m(Field::Point(7), Field::Point(6))
+                ]
+            )?,
+            "13/7* 8/7"
+        );
+        assert!(format_with_hits(
+            &board,
+            Player::Player0,
+            &[m(Field::Point(2), Field::Point(1))]
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        for s in [
+            "24/18 13/11",
+            "bar/20 6/off",
+            "13/11(2) 6/4(2)",
+            "8/2(3) 6/off",
+            "bar/22 24/23 13/7 6/off",
+            "24/18/13",
+            "24/21/18(2)",
+            "bar/22/16 13/7",
+            "13/11 24/21/18(2) 6/4",
+        ] {
+            assert_eq!(format(&parse(s)?), s);
+        }
+
+        let mut board = Board::new();
+        board.set(Player::Player1, 17, 1)?;
+        let s = "13/7* 8/7";
+        assert_eq!(format_with_hits(&board, Player::Player0, &parse(s)?)?, s);
+
+        let mut board = Board::new();
+        board.set(Player::Player1, 6, 1)?;
+        let s = "24/18*/13";
+        assert_eq!(format_with_hits(&board, Player::Player0, &parse(s)?)?, s);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 /// Implements the board
 mod board;
-pub use board::{Board, BoardDisplay, Move};
+pub use board::{Board, BoardDisplay, CheckerMove, Field, Move};
 /// Implements the double dice or cube
 mod cube;
 pub use cube::Cube;
@@ -9,7 +9,10 @@ mod player;
 pub use player::Player;
 /// Implements the pair of dices
 mod dices;
-pub use dices::{Dices, Roll};
+pub use dices::{DiceSource, Dices, RandomDices, Roll, ScriptedDices, SeededDices};
+/// Implements provably fair dices
+mod fair_dices;
+pub use fair_dices::FairDices;
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -17,9 +20,11 @@ use std::fmt;
 /// Holds all the rule settings
 #[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 pub struct Rules {
-    /// The amount of points to reach for declaring a winner of the match, default is 7.
+    /// The amount of points to reach for declaring a winner of the match, default is 7. A value of
+    /// 0 means money play, where there is no point target.
     pub points: u32,
-    /// When offered the cube, allow to re-double but keep it, default is false.
+    /// When offered the cube, allow to re-double but keep it, default is false. Only applies to
+    /// money play.
     pub beaver: bool,
     /// If a player plays "beaver", the other may double again, letting the opponent keep the cube.
     /// Default is false
@@ -66,6 +71,13 @@ impl fmt::Display for Rules {
     }
 }
 
+impl Rules {
+    /// Returns true if the rules describe money play, i.e. there is no point target
+    pub fn money_play(&self) -> bool {
+        self.points == 0
+    }
+}
+
 /// Allows to modify the rules
 pub trait MatchRules {
     /// Set the amount of points to reach for declaring a winner
@@ -183,6 +195,12 @@ mod tests {
         assert!(rules.raccoon);
     }
 
+    #[test]
+    fn test_money_play() {
+        assert!(!Rules::default().money_play());
+        assert!(Rules::default().with_points(0).money_play());
+    }
+
     #[test]
     fn test_display() {
         let rules = Rules::default();
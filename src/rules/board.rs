@@ -1,12 +1,15 @@
 use crate::rules::Player;
 use crate::Error;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
 
 /// Represents the Backgammon board
 ///
 /// A Backgammon board consists of 24 fields, each of which can hold 0 or more checkers. In
 /// addition there is a bar to hold checkers that have been hit and an off area to hold checkers
-/// that have been removed from the board.
+/// that have been removed from the board. [`Board::to_ascii`] draws the board as follows:
 ///
 /// ```
 /// # fn foo() {}
@@ -25,7 +28,7 @@ use serde::{Deserialize, Serialize};
 /// //        +13-14-15-16-17-18------19-20-21-22-23-24-+
 /// ```
 
-#[derive(Debug, Clone, Serialize, PartialEq, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash, Deserialize, Default)]
 pub struct Board {
     raw_board: (PlayerBoard, PlayerBoard),
 }
@@ -43,19 +46,107 @@ pub struct BoardDisplay {
     pub off: (u8, u8),
 }
 
+/// Represents a location a checker can move from or to.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Field {
+    /// The bar, where hit checkers wait to re-enter the board
+    Bar,
+    /// A field on the board, numbered from 0 to 23 from the player's perspective as in
+    /// [`Board::set`]
+    Point(usize),
+    /// Off the board, where checkers go when they are borne off
+    Off,
+}
+
+/// Represents the move of a single checker from one field to another.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct CheckerMove {
+    /// Where the checker starts from
+    pub from: Field,
+    /// Where the checker ends up
+    pub to: Field,
+}
+
+impl CheckerMove {
+    /// Create a new checker move
+    pub fn new(from: Field, to: Field) -> Self {
+        CheckerMove { from, to }
+    }
+}
+
+// implement Display trait, using the standard notation with points numbered from 1 to 24
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::Bar => write!(f, "bar"),
+            Field::Point(field) => write!(f, "{}", field + 1),
+            Field::Off => write!(f, "off"),
+        }
+    }
+}
+
+// implement Display trait, using the standard notation
+impl fmt::Display for CheckerMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.from, self.to)
+    }
+}
+
 impl Board {
     /// Create a new board
     pub fn new() -> Self {
         Board::default()
     }
 
+    /// Create a board from the checkers of both players.
+    ///
+    /// For each player, the array holds the amount of checkers on the fields 0 to 23 from the
+    /// player's perspective (see [`Board::set`]), followed by the checkers on the bar at index 24.
+    /// All remaining checkers of the 15 are off the board. Returns an error if a player has more
+    /// than 15 checkers or if both players have checkers on the same field.
+    pub fn from_checkers(player0: &[u8; 25], player1: &[u8; 25]) -> Result<Self, Error> {
+        let player_board = |checkers: &[u8; 25]| -> Result<PlayerBoard, Error> {
+            let total: u32 = checkers.iter().map(|&c| c as u32).sum();
+            if total > 15 {
+                return Err(Error::PositionInvalid);
+            }
+            let mut board = [0; 24];
+            board.copy_from_slice(&checkers[..24]);
+            Ok(PlayerBoard {
+                board,
+                bar: checkers[24],
+                off: 15 - total as u8,
+            })
+        };
+
+        let raw_board = (player_board(player0)?, player_board(player1)?);
+        for field in 0..24 {
+            if raw_board.0.board[field] > 0 && raw_board.1.board[23 - field] > 0 {
+                return Err(Error::PositionInvalid);
+            }
+        }
+
+        Ok(Board { raw_board })
+    }
+
+    /// Get the checkers of a player: the amount of checkers on the fields 0 to 23 from the
+    /// player's perspective, followed by the checkers on the bar at index 24. This is the inverse
+    /// of [`Board::from_checkers`].
+    pub fn checkers(&self, player: Player) -> Result<[u8; 25], Error> {
+        let pb = self.player_board(player)?;
+        let mut checkers = [0; 25];
+        checkers[..24].copy_from_slice(&pb.board);
+        checkers[24] = pb.bar;
+        Ok(checkers)
+    }
+
     /// Get the board for both players. Use for graphical representation of the board.
     ///
     /// This method outputs a tuple with three values:
     ///
     /// 1. the board represented as an array of 24 fields, each of which can hold 0 or more
-    /// checkers. Positive amounts represent checkers of player 0, negative amounts represent
-    /// checkers of player 1.
+    ///    checkers. Positive amounts represent checkers of player 0, negative amounts represent
+    ///    checkers of player 1.
     /// 2. the bar for both players
     /// 3. the off for both players
     pub fn get(&self) -> BoardDisplay {
@@ -195,10 +286,276 @@ impl Board {
             Player::Nobody => Err(Error::PlayerInvalid),
         }
     }
+
+    /// Get the board of one player
+    fn player_board(&self, player: Player) -> Result<&PlayerBoard, Error> {
+        match player {
+            Player::Player0 => Ok(&self.raw_board.0),
+            Player::Player1 => Ok(&self.raw_board.1),
+            Player::Nobody => Err(Error::PlayerInvalid),
+        }
+    }
+
+    /// Get the amount of checkers a player has borne off
+    pub fn off(&self, player: Player) -> Result<u8, Error> {
+        Ok(self.player_board(player)?.off)
+    }
+
+    /// Count the checkers of a player on the bar or in the opponent's home board (fields 18 to 23
+    /// from the player's perspective)
+    pub fn checkers_in_opponent_home(&self, player: Player) -> Result<u8, Error> {
+        let pb = self.player_board(player)?;
+        Ok(pb.bar + pb.board[18..].iter().sum::<u8>())
+    }
+
+    /// Get the pip count of a player, i.e. the total amount of pips the player needs to bear off
+    /// all checkers. Checkers on the bar count 25 pips.
+    pub fn pip_count(&self, player: Player) -> Result<u32, Error> {
+        let pb = self.player_board(player)?;
+        Ok(pb
+            .board
+            .iter()
+            .enumerate()
+            .map(|(field, &c)| (field as u32 + 1) * c as u32)
+            .sum::<u32>()
+            + 25 * pb.bar as u32)
+    }
+
+    /// Get the pip count of a player minus the pip count of the opponent. A negative value means
+    /// that the player leads the race.
+    pub fn pip_difference(&self, player: Player) -> Result<i32, Error> {
+        Ok(self.pip_count(player)? as i32 - self.pip_count(player.other())? as i32)
+    }
+
+    /// Count the checkers of a player in the home board (fields 0 to 5)
+    pub fn home_checkers(&self, player: Player) -> Result<u8, Error> {
+        Ok(self.player_board(player)?.board[..6].iter().sum())
+    }
+
+    /// Estimate the pips a player will waste when bearing off, using the adjustments of the Keith
+    /// count: 2 pips for each checker more than one on field 0, 1 pip for each checker more than
+    /// one on field 1, 1 pip for each checker more than three on field 2 and 1 pip for each empty
+    /// field among fields 3 to 5.
+    pub fn wastage(&self, player: Player) -> Result<u32, Error> {
+        let board = &self.player_board(player)?.board;
+        Ok(2 * board[0].saturating_sub(1) as u32
+            + board[1].saturating_sub(1) as u32
+            + board[2].saturating_sub(3) as u32
+            + board[3..6].iter().filter(|&&c| c == 0).count() as u32)
+    }
+
+    /// Returns the field of the rearmost checker of a player, 24 if a checker is on the bar, or
+    /// none if all checkers are off
+    fn rearmost(&self, player: Player) -> Result<Option<usize>, Error> {
+        let pb = self.player_board(player)?;
+        if pb.bar > 0 {
+            return Ok(Some(24));
+        }
+        Ok(pb.board.iter().rposition(|&c| c > 0))
+    }
+
+    /// Check if there is still contact between the two players, i.e. if a checker of one player
+    /// still has to pass a checker of the other player. Without contact, the game is a pure race.
+    pub fn contact(&self) -> bool {
+        match (
+            self.rearmost(Player::Player0).ok().flatten(),
+            self.rearmost(Player::Player1).ok().flatten(),
+        ) {
+            (Some(rear0), Some(rear1)) => rear0 + rear1 > 23,
+            _ => false,
+        }
+    }
+
+    /// Check if all checkers of a player are in the home board (fields 0 to 5) or already off.
+    pub fn all_home(&self, player: Player) -> Result<bool, Error> {
+        let pb = self.player_board(player)?;
+        Ok(pb.bar == 0 && pb.board[6..].iter().all(|&c| c == 0))
+    }
+
+    /// Check if a player is allowed to bear off a checker from a field with the given dice.
+    ///
+    /// A checker may only be borne off if all checkers of the player are in the home board
+    /// (fields 0 to 5). It is borne off with the exact number, i.e. from field 0 with a 1, or with a
+    /// larger number if there is no checker on a higher field.
+    pub fn bear_off_permitted(&self, player: Player, from: usize, dice: u8) -> Result<bool, Error> {
+        if from > 5 {
+            return Ok(false);
+        }
+        if !self.all_home(player)? {
+            return Ok(false);
+        }
+
+        let pb = self.player_board(player)?;
+        if pb.board[from] == 0 {
+            return Ok(false);
+        }
+
+        let exact = from + 1;
+        match (dice as usize).cmp(&exact) {
+            Ordering::Equal => Ok(true),
+            Ordering::Greater => Ok(pb.board[from + 1..].iter().all(|&c| c == 0)),
+            Ordering::Less => Ok(false),
+        }
+    }
+
+    /// Compute the move of a single checker of a player from a field with the given dice.
+    ///
+    /// This method checks whether the move is permitted on the current board, i.e. whether there
+    /// is a checker to move, whether checkers on the bar have to enter first, whether the target
+    /// field is blocked and whether the checker may be borne off. It does not modify the board.
+    pub fn step(&self, player: Player, from: Field, dice: u8) -> Result<CheckerMove, Error> {
+        if !(1..=6).contains(&dice) {
+            return Err(Error::DiceInvalid);
+        }
+
+        let pb = self.player_board(player)?;
+
+        let to = match from {
+            Field::Bar => {
+                if pb.bar == 0 {
+                    return Err(Error::MoveInvalid);
+                }
+                Field::Point(24 - dice as usize)
+            }
+            Field::Point(field) => {
+                if field > 23 {
+                    return Err(Error::FieldInvalid);
+                }
+                if pb.bar > 0 {
+                    return Err(Error::MoveInvalidBar);
+                }
+                if pb.board[field] == 0 {
+                    return Err(Error::MoveInvalid);
+                }
+                if field >= dice as usize {
+                    Field::Point(field - dice as usize)
+                } else if self.bear_off_permitted(player, field, dice)? {
+                    Field::Off
+                } else {
+                    return Err(Error::MoveInvalidOff);
+                }
+            }
+            Field::Off => return Err(Error::MoveInvalid),
+        };
+
+        if let Field::Point(field) = to {
+            if self.blocked(player, field)? {
+                return Err(Error::FieldBlocked);
+            }
+        }
+
+        Ok(CheckerMove::new(from, to))
+    }
+
+    /// Apply the move of a single checker of a player to the board. An opponent's checker on the
+    /// target field is hit and moved to the bar.
+    ///
+    /// This method does not check whether the move is legal for a given dice, use
+    /// [`Board::step`] for that.
+    pub fn apply_move(&mut self, player: Player, m: CheckerMove) -> Result<(), Error> {
+        match m.from {
+            Field::Bar => self.set_bar(player, -1)?,
+            Field::Point(field) => self.set(player, field, -1)?,
+            Field::Off => return Err(Error::MoveInvalid),
+        }
+
+        match m.to {
+            Field::Bar => Err(Error::MoveInvalid),
+            Field::Point(field) => self.set(player, field, 1),
+            Field::Off => self.set_off(player, 1),
+        }
+    }
+
+    /// List all legal plays of a player for the given dice values.
+    ///
+    /// A play is the full sequence of checker moves a player makes in one turn. For doubles, pass
+    /// the dice value four times (or as often as it remains to be played). Only plays using the
+    /// maximum possible amount of dices are returned; if only one of two different dices can be
+    /// played, the larger one has to be used whenever possible. Plays leading to the same
+    /// position are returned only once. If no move is possible, a single empty play is returned.
+    pub fn legal_plays(
+        &self,
+        player: Player,
+        dices: &[u8],
+    ) -> Result<Vec<Vec<CheckerMove>>, Error> {
+        let _ = self.player_board(player)?;
+
+        let mut orders = vec![dices.to_vec()];
+        if dices.len() == 2 && dices[0] != dices[1] {
+            orders.push(vec![dices[1], dices[0]]);
+        }
+
+        let mut candidates = Vec::new();
+        for order in orders {
+            self.collect_plays(player, &order, &mut Vec::new(), &mut candidates)?;
+        }
+
+        // maximize the amount of dices used
+        let max = candidates.iter().map(|(p, _)| p.len()).max().unwrap_or(0);
+        candidates.retain(|(p, _)| p.len() == max);
+
+        // if only one of two different dices can be used, it has to be the larger one
+        if max == 1 && dices.len() == 2 && dices[0] != dices[1] {
+            let larger = dices[0].max(dices[1]);
+            if candidates.iter().any(|(p, _)| p[0].1 == larger) {
+                candidates.retain(|(p, _)| p[0].1 == larger);
+            }
+        }
+
+        // deduplicate by resulting position
+        let mut seen = HashSet::new();
+        Ok(candidates
+            .into_iter()
+            .filter(|(_, board)| seen.insert(board.clone()))
+            .map(|(p, _)| p.into_iter().map(|(m, _)| m).collect())
+            .collect())
+    }
+
+    /// Recursively collect all sequences of checker moves for the dices in the given order,
+    /// together with the position they lead to.
+    fn collect_plays(
+        &self,
+        player: Player,
+        dices: &[u8],
+        play: &mut Vec<(CheckerMove, u8)>,
+        plays: &mut Vec<(Vec<(CheckerMove, u8)>, Board)>,
+    ) -> Result<(), Error> {
+        let mut moved = false;
+
+        if let Some((&dice, rest)) = dices.split_first() {
+            let pb = self.player_board(player)?;
+            let sources: Vec<Field> = if pb.bar > 0 {
+                vec![Field::Bar]
+            } else {
+                (0..24)
+                    .rev()
+                    .filter(|&f| pb.board[f] > 0)
+                    .map(Field::Point)
+                    .collect()
+            };
+
+            for from in sources {
+                if let Ok(m) = self.step(player, from, dice) {
+                    let mut board = self.clone();
+                    board.apply_move(player, m)?;
+                    play.push((m, dice));
+                    board.collect_plays(player, rest, play, plays)?;
+                    let _ = play.pop();
+                    moved = true;
+                }
+            }
+        }
+
+        if !moved {
+            plays.push((play.clone(), self.clone()));
+        }
+
+        Ok(())
+    }
 }
 
 /// Represents the Backgammon board for one player
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 struct PlayerBoard {
     board: [u8; 24],
     bar: u8,
@@ -472,4 +829,411 @@ mod tests {
         let mut board = Board::new();
         assert!(board.set(Player::Player1, 23, -3).is_err());
     }
+
+    fn board_with(p0: [u8; 24], bar0: u8, p1: [u8; 24], bar1: u8) -> Board {
+        let off = |b: &[u8; 24], bar: u8| 15 - b.iter().sum::<u8>() - bar;
+        Board {
+            raw_board: (
+                PlayerBoard {
+                    board: p0,
+                    bar: bar0,
+                    off: off(&p0, bar0),
+                },
+                PlayerBoard {
+                    board: p1,
+                    bar: bar1,
+                    off: off(&p1, bar1),
+                },
+            ),
+        }
+    }
+
+    #[test]
+    fn display_checker_move() {
+        assert_eq!(
+            format!("{}", CheckerMove::new(Field::Point(23), Field::Point(17))),
+            "24/18"
+        );
+        assert_eq!(
+            format!("{}", CheckerMove::new(Field::Bar, Field::Point(19))),
+            "bar/20"
+        );
+        assert_eq!(
+            format!("{}", CheckerMove::new(Field::Point(5), Field::Off)),
+            "6/off"
+        );
+    }
+
+    #[test]
+    fn from_checkers() -> Result<(), Error> {
+        let board = Board::new();
+        let p0 = board.checkers(Player::Player0)?;
+        let p1 = board.checkers(Player::Player1)?;
+        assert_eq!(p0[23], 2);
+        assert_eq!(p0[24], 0);
+        assert_eq!(Board::from_checkers(&p0, &p1)?, board);
+
+        let mut p0 = [0; 25];
+        p0[24] = 2;
+        p0[3] = 1;
+        let board = Board::from_checkers(&p0, &p1)?;
+        assert_eq!(board.get().bar, (2, 0));
+        assert_eq!(board.get().off, (12, 0));
+        assert_eq!(board.checkers(Player::Player0)?, p0);
+        Ok(())
+    }
+
+    #[test]
+    fn from_checkers_invalid() {
+        let mut p0 = [0; 25];
+        p0[5] = 16;
+        assert!(matches!(
+            Board::from_checkers(&p0, &[0; 25]),
+            Err(Error::PositionInvalid)
+        ));
+
+        let mut p0 = [0; 25];
+        p0[5] = 1;
+        let mut p1 = [0; 25];
+        p1[18] = 1;
+        assert!(matches!(
+            Board::from_checkers(&p0, &p1),
+            Err(Error::PositionInvalid)
+        ));
+    }
+
+    #[test]
+    fn step_point() -> Result<(), Error> {
+        let board = Board::new();
+        assert_eq!(
+            board.step(Player::Player0, Field::Point(7), 3)?,
+            CheckerMove::new(Field::Point(7), Field::Point(4))
+        );
+        assert!(board.step(Player::Player0, Field::Point(12), 1).is_err());
+        assert!(board.step(Player::Player0, Field::Point(3), 1).is_err());
+        assert!(board.step(Player::Player0, Field::Bar, 1).is_err());
+        assert!(board.step(Player::Player0, Field::Point(7), 7).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn apply_move_hit() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set(Player::Player1, 3, 1)?;
+        board.apply_move(
+            Player::Player0,
+            CheckerMove::new(Field::Point(23), Field::Point(20)),
+        )?;
+        assert_eq!(board.get().board[20], 1);
+        assert_eq!(board.get().bar.1, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_opening() -> Result<(), Error> {
+        let board = Board::new();
+        let plays = board.legal_plays(Player::Player0, &[3, 1])?;
+        assert!(plays.iter().all(|p| p.len() == 2));
+        assert!(plays.iter().any(|p| p
+            .contains(&CheckerMove::new(Field::Point(7), Field::Point(4)))
+            && p.contains(&CheckerMove::new(Field::Point(5), Field::Point(4)))));
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_deduplicated() -> Result<(), Error> {
+        let board = Board::new();
+        let plays = board.legal_plays(Player::Player1, &[3, 1])?;
+        let mut positions = HashSet::new();
+        for play in &plays {
+            let mut b = board.clone();
+            for m in play {
+                b.apply_move(Player::Player1, *m)?;
+            }
+            assert!(positions.insert(b));
+        }
+        // 8/5 6/5 and 6/5 8/5 lead to the same position and are listed once
+        assert_eq!(
+            plays
+                .iter()
+                .filter(
+                    |p| p.contains(&CheckerMove::new(Field::Point(7), Field::Point(4)))
+                        && p.contains(&CheckerMove::new(Field::Point(5), Field::Point(4)))
+                )
+                .count(),
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_doubles() -> Result<(), Error> {
+        let board = Board::new();
+        let plays = board.legal_plays(Player::Player0, &[6, 6, 6, 6])?;
+        assert!(!plays.is_empty());
+        assert!(plays.iter().all(|p| p.len() == 4));
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_bar() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[5] = 5;
+        p0[12] = 5;
+        p0[7] = 4;
+        let mut p1 = [0; 24];
+        p1[5] = 2;
+        p1[12] = 13;
+        let board = board_with(p0, 1, p1, 0);
+        let plays = board.legal_plays(Player::Player0, &[6, 2])?;
+        // the checker has to enter with the 2, as field 18 is blocked for the 6
+        assert!(plays
+            .iter()
+            .all(|p| p[0] == CheckerMove::new(Field::Bar, Field::Point(22))));
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_dance() -> Result<(), Error> {
+        let p0 = [
+            0, 0, 0, 0, 0, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let p1 = [
+            2, 2, 2, 2, 2, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let board = board_with(p0, 1, p1, 0);
+        assert_eq!(
+            board.legal_plays(Player::Player0, &[4, 4, 4, 4])?,
+            vec![Vec::<CheckerMove>::new()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_larger_dice() -> Result<(), Error> {
+        // a single checker on field 8, opponent blocks fields 2, 3 and 6 (from player 0's view),
+        // so either the 5 or the 2 can be played, but not both
+        let mut p0 = [0; 24];
+        p0[8] = 1;
+        p0[0] = 14;
+        let mut p1 = [0; 24];
+        p1[23 - 2] = 2;
+        p1[23 - 1] = 2;
+        p1[23 - 6] = 2;
+        p1[12] = 9;
+        let board = board_with(p0, 0, p1, 0);
+        let plays = board.legal_plays(Player::Player0, &[5, 2])?;
+        assert_eq!(
+            plays,
+            vec![vec![CheckerMove::new(Field::Point(8), Field::Point(3))]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_bear_off() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[0] = 3;
+        p0[3] = 2;
+        let mut p1 = [0; 24];
+        p1[5] = 15;
+        let board = board_with(p0, 0, p1, 0);
+        let plays = board.legal_plays(Player::Player0, &[6, 1])?;
+        assert!(plays.contains(&vec![
+            CheckerMove::new(Field::Point(3), Field::Off),
+            CheckerMove::new(Field::Point(0), Field::Off)
+        ]));
+        assert!(plays.iter().all(|p| p.len() == 2));
+        Ok(())
+    }
+
+    #[test]
+    fn legal_plays_invalid_player() {
+        let board = Board::new();
+        assert!(board.legal_plays(Player::Nobody, &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn all_home() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[0] = 10;
+        p0[5] = 5;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        assert!(board_with(p0, 0, p1, 0).all_home(Player::Player0)?);
+        assert!(!board_with(p0, 0, p1, 0).all_home(Player::Player1)?);
+        p0[5] = 4;
+        assert!(!board_with(p0, 1, p1, 0).all_home(Player::Player0)?);
+        p0[6] = 1;
+        assert!(!board_with(p0, 0, p1, 0).all_home(Player::Player0)?);
+        assert!(!Board::new().all_home(Player::Player0)?);
+        assert!(Board::new().all_home(Player::Nobody).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn bear_off_not_all_home() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[2] = 14;
+        p0[6] = 1;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        let board = board_with(p0, 0, p1, 0);
+        assert!(!board.bear_off_permitted(Player::Player0, 2, 3)?);
+        assert!(matches!(
+            board.step(Player::Player0, Field::Point(2), 3),
+            Err(Error::MoveInvalidOff)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn bear_off_bar() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[2] = 14;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        let board = board_with(p0, 1, p1, 0);
+        assert!(!board.bear_off_permitted(Player::Player0, 2, 3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn bear_off_exact() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[1] = 5;
+        p0[4] = 10;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        let board = board_with(p0, 0, p1, 0);
+        assert!(board.bear_off_permitted(Player::Player0, 1, 2)?);
+        assert!(board.bear_off_permitted(Player::Player0, 4, 5)?);
+        assert!(!board.bear_off_permitted(Player::Player0, 1, 1)?);
+        assert!(!board.bear_off_permitted(Player::Player0, 0, 1)?);
+        assert_eq!(
+            board.step(Player::Player0, Field::Point(1), 2)?,
+            CheckerMove::new(Field::Point(1), Field::Off)
+        );
+        assert_eq!(
+            board.step(Player::Player0, Field::Point(1), 1)?,
+            CheckerMove::new(Field::Point(1), Field::Point(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bear_off_larger_dice() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[1] = 5;
+        p0[3] = 10;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        let board = board_with(p0, 0, p1, 0);
+        // the highest checker may be borne off with a larger dice
+        assert!(board.bear_off_permitted(Player::Player0, 3, 6)?);
+        // but not a lower one while there is a checker on a higher field
+        assert!(!board.bear_off_permitted(Player::Player0, 1, 6)?);
+        assert!(matches!(
+            board.step(Player::Player0, Field::Point(1), 6),
+            Err(Error::MoveInvalidOff)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn bear_off_player1() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[12] = 15;
+        let mut p1 = [0; 24];
+        p1[0] = 15;
+        let mut board = board_with(p0, 0, p1, 0);
+        let m = board.step(Player::Player1, Field::Point(0), 4)?;
+        assert_eq!(m, CheckerMove::new(Field::Point(0), Field::Off));
+        board.apply_move(Player::Player1, m)?;
+        assert_eq!(board.get().off.1, 1);
+        assert_eq!(board.get().board[23], -14);
+        Ok(())
+    }
+
+    #[test]
+    fn off() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set_off(Player::Player1, 3)?;
+        assert_eq!(board.off(Player::Player0)?, 0);
+        assert_eq!(board.off(Player::Player1)?, 3);
+        assert!(board.off(Player::Nobody).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn checkers_in_opponent_home() -> Result<(), Error> {
+        let mut board = Board::new();
+        assert_eq!(board.checkers_in_opponent_home(Player::Player0)?, 2);
+        board.set_bar(Player::Player0, 1)?;
+        assert_eq!(board.checkers_in_opponent_home(Player::Player0)?, 3);
+        assert_eq!(board.checkers_in_opponent_home(Player::Player1)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn pip_count() -> Result<(), Error> {
+        let mut board = Board::new();
+        assert_eq!(board.pip_count(Player::Player0)?, 167);
+        assert_eq!(board.pip_count(Player::Player1)?, 167);
+        assert_eq!(board.pip_difference(Player::Player0)?, 0);
+        board.apply_move(
+            Player::Player0,
+            CheckerMove::new(Field::Point(23), Field::Point(17)),
+        )?;
+        assert_eq!(board.pip_count(Player::Player0)?, 161);
+        assert_eq!(board.pip_difference(Player::Player0)?, -6);
+        assert_eq!(board.pip_difference(Player::Player1)?, 6);
+        board.set_bar(Player::Player1, 1)?;
+        assert_eq!(board.pip_count(Player::Player1)?, 192);
+        assert!(board.pip_count(Player::Nobody).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn home_checkers() -> Result<(), Error> {
+        let board = Board::new();
+        assert_eq!(board.home_checkers(Player::Player0)?, 5);
+        assert_eq!(board.home_checkers(Player::Player1)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn wastage() -> Result<(), Error> {
+        let mut p0 = [0; 24];
+        p0[0] = 3;
+        p0[1] = 2;
+        p0[2] = 5;
+        p0[5] = 5;
+        let mut p1 = [0; 24];
+        p1[5] = 15;
+        let board = board_with(p0, 0, p1, 0);
+        assert_eq!(board.wastage(Player::Player0)?, 4 + 1 + 2 + 2);
+        assert_eq!(board.wastage(Player::Player1)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn contact() {
+        assert!(Board::new().contact());
+
+        let mut p0 = [0; 24];
+        p0[10] = 15;
+        let mut p1 = [0; 24];
+        p1[12] = 15;
+        assert!(!board_with(p0, 0, p1, 0).contact());
+        p1[14] = 1;
+        p1[12] = 14;
+        assert!(board_with(p0, 0, p1, 0).contact());
+
+        let mut p1 = [0; 24];
+        p1[0] = 14;
+        assert!(board_with(p0, 0, p1, 1).contact());
+        let p1 = [0; 24];
+        assert!(!board_with(p0, 0, p1, 0).contact());
+    }
 }
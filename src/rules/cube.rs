@@ -31,8 +31,7 @@ impl Cube {
     /// technical limit of 2^64 on the value of the cube, which we believe is a reasonable limit.
     pub fn set(&mut self, value: u64) -> Result<(), Error> {
         if value.is_power_of_two() {
-            let vf = value as f64;
-            self.exponential = vf.sqrt().round() as u8;
+            self.exponential = value.trailing_zeros() as u8;
 
             Ok(())
         } else {
@@ -97,6 +96,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_value64() -> Result<(), Error> {
+        let mut cube = Cube::default();
+        cube.set(32)?;
+        assert_eq!(cube.value(), 32);
+        cube.set(64)?;
+        assert_eq!(cube.value(), 64);
+        cube.set(1)?;
+        assert_eq!(cube.value(), 1);
+        Ok(())
+    }
+
     #[test]
     fn set_invalidvalue() -> Result<(), Error> {
         let mut cube = Cube::default();
@@ -1,6 +1,9 @@
 use crate::Error;
 use rand::distributions::{Distribution, Uniform};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// Represents the two dices
 ///
@@ -18,11 +21,21 @@ impl Dices {
     /// Roll the dices which generates two random numbers between 1 and 6, replicating a perfect
     /// dice. We use the operating system's random number generator.
     pub fn roll(self) -> Self {
-        let between = Uniform::new_inclusive(1, 6);
-        let mut rng = rand::thread_rng();
+        // the random source always rolls valid values
+        self.roll_with(&mut RandomDices).unwrap_or(self)
+    }
 
-        let v = (between.sample(&mut rng), between.sample(&mut rng));
+    /// Roll the dices, drawing the values from the given source.
+    pub fn roll_with(self, source: &mut dyn DiceSource) -> Result<Self, Error> {
+        let v = source.next_roll()?;
+        if !(1..=6).contains(&v.0) || !(1..=6).contains(&v.1) {
+            return Err(Error::DiceInvalid);
+        }
+        Ok(Dices::from_values(v))
+    }
 
+    /// Create freshly rolled dices with the given values
+    pub(crate) fn from_values(v: (u8, u8)) -> Self {
         // if both dices are equal, we have four dices to play
         if v.0 == v.1 {
             Dices {
@@ -36,12 +49,122 @@ impl Dices {
             }
         }
     }
+
+    /// Returns the dice values which have not been consumed yet. For doubles, the value is
+    /// returned up to four times.
+    pub fn remaining(&self) -> Vec<u8> {
+        let mut remaining = Vec::new();
+        if !self.consumed.0 {
+            remaining.push(self.values.0);
+        }
+        if !self.consumed.1 {
+            remaining.push(self.values.1);
+        }
+        if !self.consumed.2 {
+            remaining.push(self.values.1);
+        }
+        if !self.consumed.3 {
+            remaining.push(self.values.1);
+        }
+        remaining
+    }
+
+    /// Mark one dice with the given value as consumed. Returns an error if no dice with this value
+    /// is left to play.
+    pub fn consume(&mut self, dice: u8) -> Result<(), Error> {
+        if dice == self.values.0 && !self.consumed.0 {
+            self.consumed.0 = true;
+        } else if dice == self.values.1 && !self.consumed.1 {
+            self.consumed.1 = true;
+        } else if dice == self.values.1 && !self.consumed.2 {
+            self.consumed.2 = true;
+        } else if dice == self.values.1 && !self.consumed.3 {
+            self.consumed.3 = true;
+        } else {
+            return Err(Error::MoveInvalid);
+        }
+        Ok(())
+    }
 }
 
 /// Trait to roll the dices
 pub trait Roll {
-    /// Roll the dices
-    fn roll(&mut self) -> Result<&mut Self, Error>;
+    /// Roll the dices, using the operating system's random number generator
+    fn roll(&mut self) -> Result<&mut Self, Error> {
+        self.roll_with(&mut RandomDices)
+    }
+
+    /// Roll the dices, drawing the values from the given source
+    fn roll_with(&mut self, source: &mut dyn DiceSource) -> Result<&mut Self, Error>;
+}
+
+/// Source of dice rolls.
+///
+/// Implement this trait to control where the values of the dices come from, e.g. to reproduce a
+/// game in tests or to replay a tournament.
+pub trait DiceSource {
+    /// Return the values of the next roll of both dices
+    fn next_roll(&mut self) -> Result<(u8, u8), Error>;
+}
+
+/// Dice source using the operating system's random number generator
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomDices;
+
+impl DiceSource for RandomDices {
+    fn next_roll(&mut self) -> Result<(u8, u8), Error> {
+        let between = Uniform::new_inclusive(1, 6);
+        let mut rng = rand::thread_rng();
+        Ok((between.sample(&mut rng), between.sample(&mut rng)))
+    }
+}
+
+/// Dice source using a pseudo random number generator initialized with a seed. The same seed
+/// always produces the same sequence of rolls, as the generator is pinned to ChaCha12.
+#[derive(Debug, Clone)]
+pub struct SeededDices {
+    rng: ChaCha12Rng,
+}
+
+impl SeededDices {
+    /// Create a new seeded dice source
+    pub fn new(seed: u64) -> Self {
+        SeededDices {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl DiceSource for SeededDices {
+    fn next_roll(&mut self) -> Result<(u8, u8), Error> {
+        Ok((self.rng.gen_range(1..=6), self.rng.gen_range(1..=6)))
+    }
+}
+
+/// Dice source returning a predetermined sequence of rolls
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedDices {
+    rolls: VecDeque<(u8, u8)>,
+}
+
+impl ScriptedDices {
+    /// Create a new scripted dice source returning the given rolls in order
+    pub fn new(rolls: Vec<(u8, u8)>) -> Self {
+        ScriptedDices {
+            rolls: rolls.into(),
+        }
+    }
+
+    /// Returns the amount of rolls left
+    pub fn remaining(&self) -> usize {
+        self.rolls.len()
+    }
+}
+
+impl DiceSource for ScriptedDices {
+    fn next_roll(&mut self) -> Result<(u8, u8), Error> {
+        self.rolls.pop_front().ok_or(Error::DiceExhausted)
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +188,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_roll_with_scripted() -> Result<(), Error> {
+        let mut source = ScriptedDices::new(vec![(3, 1), (4, 4)]);
+        let dices = Dices::default().roll_with(&mut source)?;
+        assert_eq!(dices.values, (3, 1));
+        assert_eq!(dices.consumed, (false, false, true, true));
+        let dices = dices.roll_with(&mut source)?;
+        assert_eq!(dices.values, (4, 4));
+        assert_eq!(dices.consumed, (false, false, false, false));
+        assert_eq!(source.remaining(), 0);
+        assert!(matches!(
+            dices.roll_with(&mut source),
+            Err(Error::DiceExhausted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_with_invalid() {
+        let mut source = ScriptedDices::new(vec![(0, 1), (3, 7)]);
+        assert!(Dices::default().roll_with(&mut source).is_err());
+        assert!(Dices::default().roll_with(&mut source).is_err());
+    }
+
+    #[test]
+    fn test_roll_with_seeded() -> Result<(), Error> {
+        let mut a = SeededDices::new(42);
+        let mut b = SeededDices::new(42);
+        for _i in 0..100 {
+            let roll = a.next_roll()?;
+            assert_eq!(roll, b.next_roll()?);
+            assert!((1..=6).contains(&roll.0) && (1..=6).contains(&roll.1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_with_random() -> Result<(), Error> {
+        let dices = Dices::default().roll_with(&mut RandomDices)?;
+        assert!(dices.values.0 >= 1 && dices.values.0 <= 6);
+        assert!(dices.values.1 >= 1 && dices.values.1 <= 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_default() -> Result<(), Error> {
+        struct Cup(Dices);
+        impl Roll for Cup {
+            fn roll_with(&mut self, source: &mut dyn DiceSource) -> Result<&mut Self, Error> {
+                self.0 = self.0.roll_with(source)?;
+                Ok(self)
+            }
+        }
+
+        let mut cup = Cup(Dices::default());
+        let values = cup.roll()?.0.values;
+        assert!((1..=6).contains(&values.0) && (1..=6).contains(&values.1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded_rolls() -> Result<(), Error> {
+        let mut source = SeededDices::new(7);
+        let rolls = (0..5)
+            .map(|_| source.next_roll())
+            .collect::<Result<Vec<(u8, u8)>, Error>>()?;
+        // stored seeds replay the same rolls only as long as the generator does not change
+        assert_eq!(rolls, vec![(3, 1), (1, 2), (1, 1), (4, 4), (4, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut dices = Dices {
+            values: (3, 5),
+            consumed: (false, false, true, true),
+        };
+        assert_eq!(dices.remaining(), vec![3, 5]);
+        dices.consumed.0 = true;
+        assert_eq!(dices.remaining(), vec![5]);
+
+        let dices = Dices {
+            values: (4, 4),
+            consumed: (false, true, false, false),
+        };
+        assert_eq!(dices.remaining(), vec![4, 4, 4]);
+    }
+
+    #[test]
+    fn test_consume() -> Result<(), Error> {
+        let mut dices = Dices {
+            values: (2, 2),
+            consumed: (false, false, false, false),
+        };
+        for _i in 0..4 {
+            dices.consume(2)?;
+        }
+        assert!(dices.remaining().is_empty());
+        assert!(dices.consume(2).is_err());
+
+        let mut dices = Dices {
+            values: (6, 1),
+            consumed: (false, false, true, true),
+        };
+        assert!(dices.consume(3).is_err());
+        dices.consume(1)?;
+        assert_eq!(dices.remaining(), vec![6]);
+        Ok(())
+    }
+
     #[test]
     fn test_roll_consumed1() {
         for _i in 0..100 {
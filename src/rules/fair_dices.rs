@@ -0,0 +1,258 @@
+use crate::rules::DiceSource;
+use crate::Error;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Provably fair dice source using a commit-reveal scheme.
+///
+/// Before the game, the server chooses a secret seed and publishes its commitment, the SHA-256
+/// hash of the seed. The players then contribute their own seeds. Each roll is derived from the
+/// hash of all seeds and the number of the roll, so neither the server nor a player can predict
+/// or steer the dices on their own. After the game, the server reveals its seed and anyone can
+/// check the rolls with [`FairDices::verify`].
+///
+/// The server seed is never serialized, a deserialized source only keeps the commitment, the
+/// client seeds and the number of rolls. It can not roll anymore. The seed is only available
+/// through [`FairDices::reveal`], which ends the session:
+/// ```
+/// use backgammon::rules::{DiceSource, FairDices};
+///
+/// let mut dices = FairDices::new(b"server secret");
+/// let commitment = dices.commitment();
+/// dices.add_client_seed(b"player 0")?;
+/// dices.add_client_seed(b"player 1")?;
+///
+/// let rolls = vec![dices.next_roll()?, dices.next_roll()?];
+/// let client_seeds = dices.client_seeds().to_vec();
+///
+/// let server_seed = dices.reveal().unwrap();
+/// assert!(FairDices::verify(&commitment, &server_seed, &client_seeds, &rolls));
+/// # Ok::<(), backgammon::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FairDices {
+    #[serde(skip)]
+    server_seed: Option<Vec<u8>>,
+    commitment: String,
+    client_seeds: Vec<Vec<u8>>,
+    rolls: u64,
+}
+
+impl FairDices {
+    /// Create a new fair dice source with the given secret server seed
+    pub fn new(server_seed: &[u8]) -> Self {
+        FairDices {
+            server_seed: Some(server_seed.to_vec()),
+            commitment: hex(&Sha256::digest(server_seed)),
+            client_seeds: Vec::new(),
+            rolls: 0,
+        }
+    }
+
+    /// Create a new fair dice source with a random server seed of 32 bytes, generated by the
+    /// operating system's random number generator
+    pub fn random() -> Self {
+        let mut seed = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut seed);
+        FairDices::new(&seed)
+    }
+
+    /// Returns the commitment to the server seed, the hexadecimal SHA-256 hash of the seed. Publish
+    /// it before the game starts.
+    pub fn commitment(&self) -> String {
+        self.commitment.clone()
+    }
+
+    /// Add the seed of a client. Seeds can only be added before the first roll.
+    pub fn add_client_seed(&mut self, seed: &[u8]) -> Result<(), Error> {
+        if self.rolls > 0 {
+            return Err(Error::GameStarted);
+        }
+        self.client_seeds.push(seed.to_vec());
+        Ok(())
+    }
+
+    /// Reveal the server seed and end the session. Only publish it after the game has ended.
+    ///
+    /// Returns `None` if the seed is not known, e.g. for a deserialized source.
+    pub fn reveal(self) -> Option<Vec<u8>> {
+        self.server_seed
+    }
+
+    /// Returns the seeds added by the clients
+    pub fn client_seeds(&self) -> &[Vec<u8>] {
+        &self.client_seeds
+    }
+
+    /// Verify a sequence of rolls against the commitment and the revealed seeds.
+    ///
+    /// Returns true if the server seed matches the commitment and all rolls have been derived
+    /// from the seeds in the given order.
+    pub fn verify(
+        commitment: &str,
+        server_seed: &[u8],
+        client_seeds: &[Vec<u8>],
+        rolls: &[(u8, u8)],
+    ) -> bool {
+        let mut dices = FairDices::new(server_seed);
+        if !dices.commitment().eq_ignore_ascii_case(commitment) {
+            return false;
+        }
+        dices.client_seeds = client_seeds.to_vec();
+
+        rolls
+            .iter()
+            .all(|&roll| dices.next_roll().is_ok_and(|r| r == roll))
+    }
+
+    /// Derive the roll with the given number from the seeds
+    fn derive(&self, server_seed: &[u8], roll: u64) -> (u8, u8) {
+        let mut hasher = Sha256::new();
+        for seed in std::iter::once(server_seed).chain(self.client_seeds.iter().map(Vec::as_slice))
+        {
+            hasher.update((seed.len() as u64).to_be_bytes());
+            hasher.update(seed);
+        }
+        hasher.update(roll.to_be_bytes());
+        let mut digest = hasher.finalize();
+
+        // map bytes to dice values, rejecting values which would introduce a bias
+        let mut values = Vec::with_capacity(2);
+        loop {
+            for &b in digest.iter() {
+                if b < 252 {
+                    values.push(b % 6 + 1);
+                    if values.len() == 2 {
+                        return (values[0], values[1]);
+                    }
+                }
+            }
+            digest = Sha256::digest(digest);
+        }
+    }
+}
+
+impl DiceSource for FairDices {
+    fn next_roll(&mut self) -> Result<(u8, u8), Error> {
+        let server_seed = self.server_seed.as_ref().ok_or(Error::DiceExhausted)?;
+        let roll = self.derive(server_seed, self.rolls);
+        self.rolls += 1;
+        Ok(roll)
+    }
+}
+
+/// Encode bytes as lowercase hexadecimal string
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment() {
+        let dices = FairDices::new(b"abc");
+        assert_eq!(
+            dices.commitment(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_deterministic() -> Result<(), Error> {
+        let mut a = FairDices::new(b"server");
+        let mut b = FairDices::new(b"server");
+        a.add_client_seed(b"client")?;
+        b.add_client_seed(b"client")?;
+        for _i in 0..100 {
+            let roll = a.next_roll()?;
+            assert_eq!(roll, b.next_roll()?);
+            assert!((1..=6).contains(&roll.0) && (1..=6).contains(&roll.1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_seed_changes_rolls() -> Result<(), Error> {
+        let mut a = FairDices::new(b"server");
+        let mut b = FairDices::new(b"server");
+        a.add_client_seed(b"client a")?;
+        b.add_client_seed(b"client b")?;
+        let rolls_a: Vec<_> = (0..20).map(|_| a.next_roll()).collect::<Result<_, _>>()?;
+        let rolls_b: Vec<_> = (0..20).map(|_| b.next_roll()).collect::<Result<_, _>>()?;
+        assert_ne!(rolls_a, rolls_b);
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_seed_after_roll() -> Result<(), Error> {
+        let mut dices = FairDices::random();
+        let _ = dices.next_roll()?;
+        assert!(matches!(
+            dices.add_client_seed(b"late"),
+            Err(Error::GameStarted)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify() -> Result<(), Error> {
+        let mut dices = FairDices::random();
+        let commitment = dices.commitment();
+        dices.add_client_seed(b"player 0")?;
+        dices.add_client_seed(b"player 1")?;
+        let rolls: Vec<_> = (0..30)
+            .map(|_| dices.next_roll())
+            .collect::<Result<_, _>>()?;
+        let seeds = dices.client_seeds().to_vec();
+        let server_seed = dices.reveal().unwrap();
+
+        assert!(FairDices::verify(&commitment, &server_seed, &seeds, &rolls));
+
+        // wrong server seed
+        assert!(!FairDices::verify(&commitment, b"other", &seeds, &rolls));
+
+        // wrong client seeds
+        assert!(!FairDices::verify(
+            &commitment,
+            &server_seed,
+            &seeds[..1],
+            &rolls
+        ));
+
+        // tampered roll
+        let mut tampered = rolls.clone();
+        tampered[10] = if tampered[10] == (1, 1) {
+            (2, 2)
+        } else {
+            (1, 1)
+        };
+        assert!(!FairDices::verify(
+            &commitment,
+            &server_seed,
+            &seeds,
+            &tampered
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialize_without_seed() -> Result<(), Error> {
+        let mut dices = FairDices::new(b"server secret");
+        dices.add_client_seed(b"client")?;
+        let _ = dices.next_roll()?;
+
+        let json = serde_json::to_string(&dices).unwrap();
+        let seed = serde_json::to_string(&b"server secret".to_vec()).unwrap();
+        assert!(!json.contains(&seed));
+        let mut restored: FairDices = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.commitment(), dices.commitment());
+        assert_eq!(restored.client_seeds(), dices.client_seeds());
+        assert!(matches!(restored.next_roll(), Err(Error::DiceExhausted)));
+        assert_eq!(restored.reveal(), None);
+        assert_eq!(dices.reveal(), Some(b"server secret".to_vec()));
+        Ok(())
+    }
+}
@@ -0,0 +1,667 @@
+use crate::rules::{CheckerMove, Field, Player, Roll, ScriptedDices};
+use crate::{Action, Error, Game, GameResult, Match};
+
+/// Represents the properties of a node, each with its identifier and values
+type Node = Vec<(String, Vec<String>)>;
+
+/// Properties of a game which are not interpreted, each with the index of the node it belongs to
+type Properties = Vec<(usize, String, Vec<String>)>;
+
+/// Root properties written and interpreted by this module
+const KNOWN: [&str; 8] = ["FF", "GM", "CA", "MI", "PW", "PB", "RU", "RE"];
+
+/// Letter of a field for a player: points are counted from the ace point of Player1 (`a`) to
+/// the ace point of Player0 (`x`), `y` is the bar and `z` is off the board
+fn field_char(player: Player, field: Field) -> char {
+    match (field, player) {
+        (Field::Bar, _) => 'y',
+        (Field::Off, _) => 'z',
+        (Field::Point(n), Player::Player0) => (b'x' - n as u8) as char,
+        (Field::Point(n), _) => (b'a' + n as u8) as char,
+    }
+}
+
+/// Field of a letter for a player, see [`field_char`]
+fn char_field(player: Player, c: char) -> Option<Field> {
+    match (c, player) {
+        ('y', _) => Some(Field::Bar),
+        ('z', _) => Some(Field::Off),
+        ('a'..='x', Player::Player0) => Some(Field::Point((b'x' - c as u8) as usize)),
+        ('a'..='x', _) => Some(Field::Point((c as u8 - b'a') as usize)),
+        _ => None,
+    }
+}
+
+/// Player of a move property, `W` for Player0 and `B` for Player1
+fn color(player: Player) -> &'static str {
+    match player {
+        Player::Player1 => "B",
+        _ => "W",
+    }
+}
+
+/// Parse a result like `W+2`, or `B+1R` for a resignation, into the winner, the points won and
+/// whether the game has been resigned
+fn parse_result(value: &str) -> Option<(Player, u64, bool)> {
+    let (winner, points) = value.split_once('+')?;
+    let winner = match winner {
+        "W" => Player::Player0,
+        "B" => Player::Player1,
+        _ => return None,
+    };
+    let (points, resigned) = match points.strip_suffix('R') {
+        Some(points) => (points, true),
+        None => (points, false),
+    };
+    Some((winner, points.parse().ok()?, resigned))
+}
+
+/// Check if a game has been ended by a resignation
+fn is_resigned(game: &Game) -> bool {
+    game.history()
+        .iter()
+        .any(|a| matches!(a, Action::Resign { .. }))
+}
+
+/// Escape a property value
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/// Write a property with its values
+fn property(id: &str, values: &[String]) -> String {
+    let mut s = String::from(id);
+    for value in values {
+        s.push_str(&format!("[{}]", escape(value)));
+    }
+    s
+}
+
+/// Parse a collection of game trees. Variations are not supported.
+fn parse_collection(sgf: &str) -> Result<Vec<Vec<Node>>, String> {
+    let mut chars = sgf.chars().peekable();
+    let mut trees = Vec::new();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            None => break,
+            Some('(') => {}
+            Some(c) => return Err(format!("unexpected '{}'", c)),
+        }
+
+        let mut nodes = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                Some(';') => {}
+                Some(')') => break,
+                Some('(') => return Err(String::from("variations are not supported")),
+                Some(c) => return Err(format!("unexpected '{}'", c)),
+                None => return Err(String::from("unexpected end")),
+            }
+
+            let mut node = Node::new();
+            loop {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                let mut id = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                    id.push(c);
+                }
+                if id.is_empty() {
+                    break;
+                }
+
+                let mut values = Vec::new();
+                loop {
+                    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                    if chars.next_if_eq(&'[').is_none() {
+                        break;
+                    }
+                    let mut value = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('\\') => value.extend(chars.next()),
+                            Some(']') => break,
+                            Some(c) => value.push(c),
+                            None => return Err(String::from("unterminated value")),
+                        }
+                    }
+                    values.push(value);
+                }
+                if values.is_empty() {
+                    return Err(format!("property {} without value", id));
+                }
+                node.push((id, values));
+            }
+            nodes.push(node);
+        }
+        trees.push(nodes);
+    }
+    Ok(trees)
+}
+
+/// Convert the history of a game into move nodes, each with the player and the value
+fn nodes(game: &Game) -> Vec<(Player, String)> {
+    let mut nodes = Vec::new();
+    let mut turn: Option<(Player, String)> = None;
+    let mut opening = true;
+
+    for action in game.history() {
+        match action {
+            Action::Roll { player, dices } => {
+                // tied opening rolls can not be recorded
+                if *player == Player::Nobody {
+                    continue;
+                }
+                let dices = if opening {
+                    (dices.0.max(dices.1), dices.0.min(dices.1))
+                } else {
+                    *dices
+                };
+                opening = false;
+                turn = Some((*player, format!("{}{}", dices.0, dices.1)));
+            }
+            Action::Move {
+                player,
+                checker_move,
+                ..
+            } => {
+                if let Some((_, value)) = turn.as_mut() {
+                    value.push(field_char(*player, checker_move.from));
+                    value.push(field_char(*player, checker_move.to));
+                }
+            }
+            Action::Commit { .. } => nodes.extend(turn.take()),
+            Action::Double { player, .. } => nodes.push((*player, String::from("double"))),
+            Action::Take { player } => nodes.push((*player, String::from("take"))),
+            Action::Drop { player } => nodes.push((*player, String::from("drop"))),
+            Action::Beaver { player, .. } => nodes.push((*player, String::from("beaver"))),
+            Action::Raccoon { player, .. } => nodes.push((*player, String::from("raccoon"))),
+            // the result is written to the root node
+            Action::MurphyDouble { .. } | Action::Resign { .. } | Action::GameEnd { .. } => {}
+        }
+    }
+
+    // the turn currently being played
+    nodes.extend(turn);
+    nodes
+}
+
+/// Replay the value of a move node on the game
+fn replay(game: &mut Game, player: Player, value: &str, last: bool) -> Result<(), Error> {
+    match value {
+        "double" => {
+            let _ = game.offer_double(player)?;
+        }
+        "take" => {
+            let _ = game.accept_double(player)?;
+        }
+        "drop" => {
+            let _ = game.reject_double(player)?;
+        }
+        "beaver" => {
+            let _ = game.beaver(player)?;
+        }
+        "raccoon" => {
+            let _ = game.raccoon(player)?;
+        }
+        _ => {
+            let chars: Vec<char> = value.chars().collect();
+            if chars.len() < 2 || chars.len() % 2 != 0 {
+                return Err(Error::MoveInvalid);
+            }
+            let dices = match (chars[0].to_digit(10), chars[1].to_digit(10)) {
+                (Some(d0), Some(d1)) => (d0 as u8, d1 as u8),
+                _ => return Err(Error::DiceInvalid),
+            };
+            let moves = chars[2..]
+                .chunks(2)
+                .map(
+                    |m| match (char_field(player, m[0]), char_field(player, m[1])) {
+                        (Some(from), Some(to)) => Ok(CheckerMove::new(from, to)),
+                        _ => Err(Error::FieldInvalid),
+                    },
+                )
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // the player who opens the game plays the larger dice
+            let dices = match (game.who_plays, player) {
+                (Player::Nobody, Player::Player0) => (dices.0.max(dices.1), dices.0.min(dices.1)),
+                (Player::Nobody, _) => (dices.0.min(dices.1), dices.0.max(dices.1)),
+                _ => dices,
+            };
+            let _ = game.roll_with(&mut ScriptedDices::new(vec![dices]))?;
+            if game.who_plays != player {
+                return Err(Error::NotYourTurn);
+            }
+
+            // the last turn of a record may not be finished yet
+            let mut played = game.apply_moves(player, &moves)?;
+            if played.turn_complete()? {
+                let _ = played.commit(player)?;
+            } else if !last {
+                return Err(Error::MoveInvalidAllDices);
+            }
+            *game = played;
+        }
+    }
+    Ok(())
+}
+
+/// Write a match with the properties of each game which are not interpreted
+fn write(m: &Match, properties: &[Properties]) -> String {
+    let mut sgf = String::new();
+
+    for (i, game) in m.games.iter().enumerate() {
+        let mut rules = Vec::new();
+        if game.rules.crawford {
+            rules.push("Crawford");
+        }
+        if game.crawford() {
+            rules.push("CrawfordGame");
+        }
+        if game.rules.jacoby {
+            rules.push("Jacoby");
+        }
+        if game.rules.beaver {
+            rules.push("Beaver");
+        }
+        if game.rules.raccoon {
+            rules.push("Raccoon");
+        }
+        if game.rules.holland {
+            rules.push("Holland");
+        }
+
+        let mut root = vec![
+            (String::from("FF"), vec![String::from("4")]),
+            (String::from("GM"), vec![String::from("6")]),
+            (String::from("CA"), vec![String::from("UTF-8")]),
+            (
+                String::from("MI"),
+                vec![
+                    format!("length:{}", game.rules.points),
+                    format!("game:{}", i),
+                    format!("ws:{}", game.score.0),
+                    format!("bs:{}", game.score.1),
+                ],
+            ),
+        ];
+        if !m.players.0.is_empty() {
+            root.push((String::from("PW"), vec![m.players.0.clone()]));
+        }
+        if !m.players.1.is_empty() {
+            root.push((String::from("PB"), vec![m.players.1.clone()]));
+        }
+        if !rules.is_empty() {
+            root.push((String::from("RU"), vec![rules.join(":")]));
+        }
+        if game.result.is_some() {
+            let resigned = is_resigned(game);
+            root.push((
+                String::from("RE"),
+                vec![format!(
+                    "{}+{}{}",
+                    color(game.winner),
+                    game.points(),
+                    if resigned { "R" } else { "" }
+                )],
+            ));
+        }
+
+        sgf.push_str("(;");
+        for (id, values) in &root {
+            sgf.push_str(&property(id, values));
+        }
+        let unknown = |sgf: &mut String, index: usize| {
+            let unknown = properties.get(i).map(|p| p.as_slice()).unwrap_or_default();
+            for (_, id, values) in unknown.iter().filter(|p| p.0 == index) {
+                sgf.push_str(&property(id, values));
+            }
+        };
+        unknown(&mut sgf, 0);
+        sgf.push('\n');
+
+        for (n, (player, value)) in nodes(game).into_iter().enumerate() {
+            sgf.push_str(&format!(";{}[{}]", color(player), value));
+            unknown(&mut sgf, n + 1);
+            sgf.push('\n');
+        }
+        sgf.push_str(")\n");
+    }
+    sgf
+}
+
+/// Match record in the Smart Game Format (SGF), together with the properties which are not
+/// interpreted by this crate, e.g. comments or analysis. Use it to exchange records with GNU
+/// Backgammon without losing information:
+/// ```
+/// use backgammon::SgfRecord;
+///
+/// let sgf = "(;FF[4]GM[6]CA[UTF-8]MI[length:0][game:0][ws:0][bs:0]C[opening]\n;W[31qtst]\n)\n";
+/// let record = SgfRecord::from_sgf(sgf)?;
+/// assert!(record.game_match.rules.money_play());
+/// assert_eq!(record.to_sgf(), sgf);
+/// # Ok::<(), backgammon::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SgfRecord {
+    /// The match of the record
+    pub game_match: Match,
+    /// properties which are not interpreted, for each game
+    properties: Vec<Properties>,
+}
+
+impl SgfRecord {
+    /// Import a match record from the Smart Game Format, see [`Match::from_sgf`]. Properties
+    /// which are not interpreted are kept with the node they were read from; properties of nodes
+    /// without a move are kept with the preceding move.
+    pub fn from_sgf(sgf: &str) -> Result<Self, Error> {
+        let trees = parse_collection(sgf).map_err(Error::SgfInvalid)?;
+        let mut m = Match::new();
+        let mut properties = Vec::new();
+
+        for (i, tree) in trees.iter().enumerate() {
+            let invalid = |node: usize, reason: &str| {
+                Error::SgfInvalid(format!("game {}, node {}: {}", i + 1, node, reason))
+            };
+            let (root, nodes) = tree.split_first().ok_or_else(|| invalid(0, "empty game"))?;
+
+            let value = |id: &str| {
+                root.iter()
+                    .find(|p| p.0 == id)
+                    .map(|p| p.1.as_slice())
+                    .unwrap_or_default()
+            };
+            if value("GM") != [String::from("6")] {
+                return Err(invalid(0, "not a backgammon record"));
+            }
+
+            let mut score = (0, 0);
+            for info in value("MI") {
+                let (key, number) = info.split_once(':').unwrap_or((info, ""));
+                let number = || number.parse().map_err(|_| invalid(0, info));
+                match key {
+                    "length" if i == 0 => m.rules.points = number()?,
+                    "ws" => score.0 = number()?,
+                    "bs" => score.1 = number()?,
+                    _ => {}
+                }
+            }
+            if i == 0 {
+                m.rules.crawford = false;
+                for rule in value("RU").iter().flat_map(|r| r.split(':')) {
+                    match rule {
+                        "Crawford" => m.rules.crawford = true,
+                        "Jacoby" => m.rules.jacoby = true,
+                        "Beaver" => m.rules.beaver = true,
+                        "Raccoon" => m.rules.raccoon = true,
+                        "Holland" => m.rules.holland = true,
+                        _ => {}
+                    }
+                }
+                m.players = (
+                    value("PW").first().cloned().unwrap_or_default(),
+                    value("PB").first().cloned().unwrap_or_default(),
+                );
+            }
+
+            let game = m.new_game().map_err(|e| invalid(0, &e.to_string()))?;
+            if game.score != score {
+                return Err(invalid(0, "score does not match the games played"));
+            }
+            let mut unknown: Properties = root
+                .iter()
+                .filter(|p| !KNOWN.contains(&p.0.as_str()))
+                .map(|p| (0, p.0.clone(), p.1.clone()))
+                .collect();
+
+            let mut index = 0;
+            for (n, node) in nodes.iter().enumerate() {
+                let is_move = |p: &&(String, Vec<String>)| p.0 == "W" || p.0 == "B";
+                if node.iter().any(|p| is_move(&p)) {
+                    index += 1;
+                }
+                for (id, values) in node.iter().filter(|p| !is_move(p)) {
+                    unknown.push((index, id.clone(), values.clone()));
+                }
+                for (id, values) in node.iter().filter(is_move) {
+                    let player = match id.as_str() {
+                        "W" => Player::Player0,
+                        _ => Player::Player1,
+                    };
+                    replay(game, player, &values[0], n + 1 == nodes.len())
+                        .map_err(|e| invalid(n + 1, &e.to_string()))?;
+                }
+            }
+
+            if let Some(result) = value("RE").first() {
+                let (winner, points, resigned) =
+                    parse_result(result).ok_or_else(|| invalid(0, "invalid result"))?;
+                // the resignation is only recorded in the result
+                if resigned && game.result.is_none() {
+                    for result in [
+                        GameResult::Single,
+                        GameResult::Gammon,
+                        GameResult::Backgammon,
+                    ] {
+                        let mut resigned = game.clone();
+                        let _ = resigned
+                            .resign(winner.other(), result)
+                            .map_err(|e| invalid(0, &e.to_string()))?;
+                        if resigned.points() == points {
+                            *game = resigned;
+                            break;
+                        }
+                    }
+                }
+                if game.result.is_none()
+                    || game.winner != winner
+                    || game.points() != points
+                    || resigned != is_resigned(game)
+                {
+                    return Err(invalid(0, "result does not match the game"));
+                }
+            }
+            properties.push(unknown);
+        }
+
+        Ok(SgfRecord {
+            game_match: m,
+            properties,
+        })
+    }
+
+    /// Export the match record to the Smart Game Format, see [`Match::to_sgf`]. The properties
+    /// which are not interpreted are written back to the node they were read from.
+    pub fn to_sgf(&self) -> String {
+        write(&self.game_match, &self.properties)
+    }
+}
+
+impl Match {
+    /// Export the match to the Smart Game Format (SGF) as used by GNU Backgammon, with one game
+    /// tree per game.
+    ///
+    /// Points are written as letters from `a` to `x`, seen from the ace point of Player1, with `y`
+    /// for the bar and `z` for checkers borne off. Player0 plays white (`W`) and Player1 black
+    /// (`B`).
+    pub fn to_sgf(&self) -> String {
+        write(self, &[])
+    }
+
+    /// Import a match from the Smart Game Format (SGF) as used by GNU Backgammon, see
+    /// [`Match::to_sgf`].
+    ///
+    /// Every game tree is replayed through the engine, so that illegal actions are reported as
+    /// errors, as is a result which does not match the replayed game. The rules and names are
+    /// taken from the first game. Properties which are not interpreted are dropped, see
+    /// [`SgfRecord`] to keep them.
+    pub fn from_sgf(sgf: &str) -> Result<Self, Error> {
+        SgfRecord::from_sgf(sgf).map(|record| record.game_match)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation;
+    use crate::rules::{MatchRules, Move};
+
+    /// Player0 wins the first game by a dropped double, the second game is still running
+    fn short_match() -> Result<Match, Error> {
+        let mut m = Match::new().with_points(3);
+        m.players = (String::from("Alice"), String::from("Bob"));
+
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = g.play(Player::Player0, &notation::parse("8/5 6/5")?)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(5, 2)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("13/8 13/11")?)?;
+        let _ = g.offer_double(Player::Player0)?;
+        let _ = g.reject_double(Player::Player1)?;
+
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(4, 6)]))?;
+        let _ = g.play(Player::Player1, &notation::parse("24/18 18/14")?)?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(2, 2)]))?;
+        let _ = g.move_checker(Player::Player0, 2, 5)?;
+        Ok(m)
+    }
+
+    const SHORT_MATCH: &str = "\
+(;FF[4]GM[6]CA[UTF-8]MI[length:3][game:0][ws:0][bs:0]PW[Alice]PB[Bob]RU[Crawford]RE[W+1]
+;W[31qtst]
+;B[52mhmk]
+;W[double]
+;B[drop]
+)
+(;FF[4]GM[6]CA[UTF-8]MI[length:3][game:1][ws:1][bs:0]PW[Alice]PB[Bob]RU[Crawford]
+;B[64xrrn]
+;W[22su]
+)
+";
+
+    #[test]
+    fn field_chars() {
+        for n in 0..24 {
+            for player in [Player::Player0, Player::Player1] {
+                let c = field_char(player, Field::Point(n));
+                assert_eq!(char_field(player, c), Some(Field::Point(n)));
+            }
+            // both players see the same point with the same letter
+            assert_eq!(
+                field_char(Player::Player0, Field::Point(n)),
+                field_char(Player::Player1, Field::Point(23 - n))
+            );
+        }
+        assert_eq!(field_char(Player::Player0, Field::Bar), 'y');
+        assert_eq!(char_field(Player::Player1, 'z'), Some(Field::Off));
+        assert_eq!(char_field(Player::Player1, '!'), None);
+    }
+
+    #[test]
+    fn to_sgf() -> Result<(), Error> {
+        assert_eq!(short_match()?.to_sgf(), SHORT_MATCH);
+        Ok(())
+    }
+
+    #[test]
+    fn from_sgf() -> Result<(), Error> {
+        let original = short_match()?;
+        let m = Match::from_sgf(SHORT_MATCH)?;
+        assert_eq!(m.rules, original.rules);
+        assert_eq!(m.players, original.players);
+        assert_eq!(m.score(), (1, 0));
+        for (game, original) in m.games.iter().zip(&original.games) {
+            assert_eq!(game.history(), original.history());
+            assert_eq!(game.board, original.board);
+        }
+        assert_eq!(m.to_sgf(), SHORT_MATCH);
+        Ok(())
+    }
+
+    #[test]
+    fn from_sgf_unknown_properties() -> Result<(), Error> {
+        let sgf = "(;FF[4]GM[6]AP[GNU Backgammon:1.07]MI[length:0][game:0][ws:0][bs:0]\
+                   DT[2024-05-01]C[a \\] comment];W[31qtst]C[good];C[x]B[52mhmk]A[ver:3])";
+        let record = SgfRecord::from_sgf(sgf)?;
+        assert!(record.game_match.rules.money_play());
+        assert_eq!(
+            record.to_sgf(),
+            "(;FF[4]GM[6]CA[UTF-8]MI[length:0][game:0][ws:0][bs:0]AP[GNU Backgammon:1.07]DT[2024-05-01]C[a \\] comment]
+;W[31qtst]C[good]
+;B[52mhmk]C[x]A[ver:3]
+)
+"
+        );
+
+        // the match alone does not keep them
+        assert_eq!(
+            Match::from_sgf(sgf)?.to_sgf(),
+            "(;FF[4]GM[6]CA[UTF-8]MI[length:0][game:0][ws:0][bs:0]\n;W[31qtst]\n;B[52mhmk]\n)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_sgf_result() -> Result<(), Error> {
+        for result in ["RE[B+1]", "RE[W+2]", "RE[W+1R]"] {
+            let sgf = SHORT_MATCH.replace("RE[W+1]", result);
+            assert!(
+                matches!(
+                    Match::from_sgf(&sgf),
+                    Err(Error::SgfInvalid(reason)) if reason == "game 1, node 0: result does not match the game"
+                ),
+                "{}",
+                result
+            );
+        }
+
+        // a resignation is only recorded in the result
+        let mut m = Match::new().with_points(3);
+        let g = m.new_game()?;
+        let _ = g.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let _ = g.play(Player::Player0, &notation::parse("8/5 6/5")?)?;
+        let _ = g.resign(Player::Player1, GameResult::Gammon)?;
+        let sgf = m.to_sgf();
+        assert!(sgf.contains("RE[W+2R]"));
+        let imported = Match::from_sgf(&sgf)?;
+        assert_eq!(imported.games[0].history(), m.games[0].history());
+        assert_eq!(imported.score(), (2, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn from_sgf_rules() -> Result<(), Error> {
+        let m = Match::from_sgf("(;GM[6]MI[length:0]RU[Jacoby:Beaver])")?;
+        assert!(m.rules.jacoby);
+        assert!(m.rules.beaver);
+        assert!(!m.rules.raccoon);
+        assert!(!m.rules.crawford);
+        Ok(())
+    }
+
+    #[test]
+    fn from_sgf_invalid() {
+        for sgf in [
+            "(;GM[1])",
+            "(;GM[6]",
+            "(;GM[6];W[31qtst](;B[52]))",
+            "(;GM[6]C[open",
+            "(;GM[6];W[31qtsu])",
+            "(;GM[6];W[31qt];B[52mhmk])",
+            "(;GM[6];B[double])",
+            "(;GM[6]MI[ws:1])",
+            "(;GM[6]RE[W+1];W[31qtst])",
+            "(;GM[6]RE[W]MI[length:0];W[31qtst])",
+        ] {
+            assert!(
+                matches!(Match::from_sgf(sgf), Err(Error::SgfInvalid(_))),
+                "{}",
+                sgf
+            );
+        }
+    }
+}
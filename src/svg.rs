@@ -0,0 +1,633 @@
+//! # SVG Board Rendering
+//!
+//! Draws a board or a game as a standalone SVG image with the points, the checkers, the bar, the
+//! bear-off trays, the cube, the dices and optional arrows for a play. The image is seen from the
+//! player on roll like the ASCII diagram of [`Board::to_ascii`]: points 1 to 12 run along the top
+//! and points 13 to 24 along the bottom. Colours and the side of the home board are set in the
+//! [`Options`]. The output only depends on the position and the options, so the same position
+//! always gives the same image.
+//! ```
+//! use backgammon::Game;
+//! use backgammon::svg::{Options, Orientation};
+//!
+//! let options = Options {
+//!     orientation: Orientation::HomeLeft,
+//!     ..Options::default()
+//! };
+//! let svg = Game::new().to_svg(&[], &options);
+//! assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+//! ```
+use crate::rules::{Board, CheckerMove, Field, Player};
+use crate::Game;
+
+/// Width of the column holding the cube
+const CUBE_COLUMN: i32 = 50;
+/// Width of the frame around the board
+const FRAME: i32 = 20;
+/// Width of a point
+const POINT: i32 = 40;
+/// Width of the bar
+const BAR: i32 = 40;
+/// Width of a bear-off tray
+const TRAY: i32 = 50;
+/// Diameter of a checker
+const CHECKER: i32 = 36;
+/// Amount of checkers drawn on a point before the rest is written as a count
+const STACK: i32 = 5;
+/// Height of a checker borne off, and the distance between two of them
+const OFF: (i32, i32) = (10, 12);
+
+/// Width of the image
+const WIDTH: i32 = CUBE_COLUMN + FRAME + 12 * POINT + BAR + FRAME + TRAY + FRAME;
+/// Height of the image
+const HEIGHT: i32 = 2 * FRAME + 2 * STACK * CHECKER + 2 * CHECKER + 8;
+/// Top and bottom edge of the points
+const EDGES: (i32, i32) = (FRAME, HEIGHT - FRAME);
+/// Vertical middle of the board
+const MIDDLE: i32 = HEIGHT / 2;
+/// Left edge of the board
+const BOARD_LEFT: i32 = CUBE_COLUMN + FRAME;
+/// Left edge of the bar
+const BAR_LEFT: i32 = BOARD_LEFT + 6 * POINT;
+/// Left edge of the bear-off trays
+const TRAY_LEFT: i32 = BAR_LEFT + BAR + 6 * POINT + FRAME;
+
+/// Represents the side of the board the home board of the player on roll is drawn on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Home board on the right, bear-off trays on the right
+    #[default]
+    HomeRight,
+    /// Home board on the left, bear-off trays on the left
+    HomeLeft,
+}
+
+/// Represents the options of the SVG image. Colours are given as SVG colours, e.g. `#ffffff` or
+/// `white`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Colour of the frame around the board
+    pub frame: String,
+    /// Colour of the board
+    pub board: String,
+    /// Alternating colours of the points
+    pub points: (String, String),
+    /// Colours of the checkers of Player0 and Player1
+    pub checkers: (String, String),
+    /// Colour of the outlines and of the cube value
+    pub lines: String,
+    /// Colour of the point numbers on the frame
+    pub labels: String,
+    /// Colour of the cube
+    pub cube: String,
+    /// Colour of the move arrows
+    pub arrows: String,
+    /// Side of the home board of the player on roll
+    pub orientation: Orientation,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            frame: String::from("#5c3a1e"),
+            board: String::from("#e8d8b0"),
+            points: (String::from("#c8a165"), String::from("#7a3b12")),
+            checkers: (String::from("#f5f5f5"), String::from("#222222")),
+            lines: String::from("#000000"),
+            labels: String::from("#f5f5f5"),
+            cube: String::from("#ffffff"),
+            arrows: String::from("#d01010"),
+            orientation: Orientation::HomeRight,
+        }
+    }
+}
+
+/// Horizontal center of the point with the given number, seen from the player on roll
+fn point_x(n: usize) -> i32 {
+    let column = if n <= 12 { 12 - n } else { n - 13 } as i32;
+    let bar = if column >= 6 { BAR } else { 0 };
+    BOARD_LEFT + column * POINT + bar + POINT / 2
+}
+
+/// Center of the checker with the given index on the point with the given number
+fn point_slot(n: usize, index: i32) -> (i32, i32) {
+    let offset = CHECKER / 2 + index.min(STACK - 1) * CHECKER;
+    if n <= 12 {
+        (point_x(n), EDGES.0 + offset)
+    } else {
+        (point_x(n), EDGES.1 - offset)
+    }
+}
+
+/// Center of the checker with the given index on the bar, of the player on roll if `own`
+fn bar_slot(own: bool, index: i32) -> (i32, i32) {
+    let offset = CHECKER + index.min(STACK - 1) * CHECKER;
+    let x = BAR_LEFT + BAR / 2;
+    if own {
+        (x, MIDDLE + offset)
+    } else {
+        (x, MIDDLE - offset)
+    }
+}
+
+/// Top left corner of the checker with the given index borne off, of the player on roll if `own`
+fn off_slot(own: bool, index: i32) -> (i32, i32) {
+    let x = TRAY_LEFT + 5;
+    if own {
+        (x, EDGES.0 + index * OFF.1)
+    } else {
+        (x, EDGES.1 - OFF.0 - index * OFF.1)
+    }
+}
+
+/// Implements an SVG image, mirrored horizontally for the home board on the left
+struct Canvas<'a> {
+    options: &'a Options,
+    elements: Vec<String>,
+}
+
+impl<'a> Canvas<'a> {
+    fn new(options: &'a Options) -> Self {
+        Canvas {
+            options,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Horizontal position of a point in the image
+    fn x(&self, x: i32) -> i32 {
+        match self.options.orientation {
+            Orientation::HomeRight => x,
+            Orientation::HomeLeft => WIDTH - x,
+        }
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, fill: &str) {
+        let x = self.x(x).min(self.x(x + width));
+        self.elements.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+            x, y, width, height, fill, self.options.lines
+        ));
+    }
+
+    fn circle(&mut self, (x, y): (i32, i32), r: i32, fill: &str) {
+        self.elements.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+            self.x(x),
+            y,
+            r,
+            fill,
+            self.options.lines
+        ));
+    }
+
+    fn triangle(&mut self, corners: [(i32, i32); 3], fill: &str) {
+        let corners: Vec<String> = corners
+            .iter()
+            .map(|&(x, y)| format!("{},{}", self.x(x), y))
+            .collect();
+        self.elements.push(format!(
+            "<polygon points=\"{}\" fill=\"{}\"/>",
+            corners.join(" "),
+            fill
+        ));
+    }
+
+    fn text(&mut self, (x, y): (i32, i32), size: i32, fill: &str, text: &str) {
+        self.elements.push(format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text>",
+            self.x(x),
+            y,
+            size,
+            fill,
+            text
+        ));
+    }
+
+    fn arrow(&mut self, from: (i32, i32), to: (i32, i32)) {
+        self.elements.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"3\" \
+             marker-end=\"url(#arrow)\"/>",
+            self.x(from.0),
+            from.1,
+            self.x(to.0),
+            to.1,
+            self.options.arrows
+        ));
+    }
+
+    /// Colour of the checkers of a player, and the colour of text on them
+    fn checker_colours(&self, player: Player) -> (String, String) {
+        let (player0, player1) = self.options.checkers.clone();
+        match player {
+            Player::Player1 => (player1, player0),
+            _ => (player0, player1),
+        }
+    }
+
+    /// Draw a stack of checkers, writing the amount on the last one if it does not fit
+    fn stack(&mut self, checkers: u8, player: Player, slot: impl Fn(i32) -> (i32, i32)) {
+        let (fill, text) = self.checker_colours(player);
+        let checkers = checkers as i32;
+        for index in 0..checkers.min(STACK) {
+            self.circle(slot(index), CHECKER / 2 - 1, &fill);
+        }
+        if checkers > STACK {
+            self.text(slot(STACK - 1), 16, &text, &checkers.to_string());
+        }
+    }
+
+    /// Draw the board, seen from `player`
+    fn board(&mut self, board: &Board, player: Player) {
+        let opponent = player.other();
+        // the checkers are always valid for Player0 and Player1
+        let own = board.checkers(player).unwrap_or_default();
+        let other = board.checkers(opponent).unwrap_or_default();
+
+        let options = self.options;
+        self.rect(0, 0, WIDTH, HEIGHT, &options.frame);
+        self.rect(
+            BOARD_LEFT,
+            EDGES.0,
+            6 * POINT,
+            EDGES.1 - EDGES.0,
+            &options.board,
+        );
+        self.rect(
+            BAR_LEFT + BAR,
+            EDGES.0,
+            6 * POINT,
+            EDGES.1 - EDGES.0,
+            &options.board,
+        );
+        for y in [EDGES.0, MIDDLE + CHECKER / 2 + 4] {
+            self.rect(
+                TRAY_LEFT,
+                y,
+                TRAY,
+                MIDDLE - CHECKER / 2 - 4 - EDGES.0,
+                &options.board,
+            );
+        }
+
+        for n in 1..=24 {
+            let fill = if n % 2 == 0 {
+                &options.points.1
+            } else {
+                &options.points.0
+            };
+            let x = point_x(n);
+            let (base, tip, label) = if n <= 12 {
+                (EDGES.0, EDGES.0 + STACK * CHECKER, EDGES.0 / 2)
+            } else {
+                (EDGES.1, EDGES.1 - STACK * CHECKER, EDGES.1 + FRAME / 2)
+            };
+            self.triangle(
+                [(x - POINT / 2, base), (x + POINT / 2, base), (x, tip)],
+                fill,
+            );
+            self.text((x, label), 12, &options.labels, &n.to_string());
+        }
+
+        for n in 1..=24 {
+            if own[n - 1] > 0 {
+                self.stack(own[n - 1], player, |i| point_slot(n, i));
+            } else if other[24 - n] > 0 {
+                self.stack(other[24 - n], opponent, |i| point_slot(n, i));
+            }
+        }
+        self.stack(own[24], player, |i| bar_slot(true, i));
+        self.stack(other[24], opponent, |i| bar_slot(false, i));
+
+        for (own, player) in [(true, player), (false, opponent)] {
+            let (fill, _) = self.checker_colours(player);
+            for index in 0..board.off(player).unwrap_or_default() as i32 {
+                let (x, y) = off_slot(own, index);
+                self.rect(x, y, TRAY - 10, OFF.0, &fill);
+            }
+        }
+    }
+
+    /// Draw the cube in the middle of its column, or on the side of its owner
+    fn cube(&mut self, value: u64, owner: Player, player: Player) {
+        let y = if owner == player {
+            EDGES.0
+        } else if owner == Player::Nobody {
+            MIDDLE - CHECKER / 2
+        } else {
+            EDGES.1 - CHECKER
+        };
+        let x = (CUBE_COLUMN - CHECKER) / 2;
+        let value = if owner == Player::Nobody && value == 1 {
+            64
+        } else {
+            value
+        };
+        let options = self.options;
+        self.rect(x, y, CHECKER, CHECKER, &options.cube);
+        self.text(
+            (x + CHECKER / 2, y + CHECKER / 2),
+            16,
+            &options.lines,
+            &value.to_string(),
+        );
+    }
+
+    /// Draw the dices of the player on roll on the right half of the board
+    fn dices(&mut self, values: (u8, u8), player: Player) {
+        let (fill, pips) = self.checker_colours(player);
+        let center = BAR_LEFT + BAR + 3 * POINT;
+        for (x, value) in [(center - CHECKER - 8, values.0), (center + 8, values.1)] {
+            let y = MIDDLE - CHECKER / 2;
+            self.rect(x, y, CHECKER, CHECKER, &fill);
+            let (low, mid, high) = (CHECKER / 4, CHECKER / 2, CHECKER * 3 / 4);
+            let spots: &[(i32, i32)] = match value {
+                1 => &[(mid, mid)],
+                2 => &[(low, low), (high, high)],
+                3 => &[(low, low), (mid, mid), (high, high)],
+                4 => &[(low, low), (high, low), (low, high), (high, high)],
+                5 => &[
+                    (low, low),
+                    (high, low),
+                    (mid, mid),
+                    (low, high),
+                    (high, high),
+                ],
+                _ => &[
+                    (low, low),
+                    (high, low),
+                    (low, mid),
+                    (high, mid),
+                    (low, high),
+                    (high, high),
+                ],
+            };
+            for &(dx, dy) in spots {
+                self.circle((x + dx, y + dy), 3, &pips);
+            }
+        }
+    }
+
+    /// Draw an arrow for each move of a play of the player on roll, starting on the last
+    /// checker of a field and pointing to the next free place
+    fn arrows(&mut self, board: &Board, player: Player, play: &[CheckerMove]) {
+        let mut own = board.checkers(player).unwrap_or_default();
+        let mut off = board.off(player).unwrap_or_default();
+        for m in play {
+            let from = match m.from {
+                Field::Point(f) if f < 24 && own[f] > 0 => {
+                    own[f] -= 1;
+                    point_slot(f + 1, own[f] as i32)
+                }
+                Field::Bar if own[24] > 0 => {
+                    own[24] -= 1;
+                    bar_slot(true, own[24] as i32)
+                }
+                _ => continue,
+            };
+            let to = match m.to {
+                Field::Point(t) if t < 24 => {
+                    own[t] += 1;
+                    point_slot(t + 1, own[t] as i32 - 1)
+                }
+                Field::Off => {
+                    off += 1;
+                    let (x, y) = off_slot(true, off as i32 - 1);
+                    (x + TRAY / 2 - 5, y + OFF.0 / 2)
+                }
+                _ => continue,
+            };
+            self.arrow(from, to);
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+             viewBox=\"0 0 {0} {1}\">\n",
+            WIDTH, HEIGHT
+        );
+        if self.elements.iter().any(|e| e.contains("url(#arrow)")) {
+            svg.push_str(&format!(
+                "<defs><marker id=\"arrow\" markerWidth=\"4\" markerHeight=\"4\" refX=\"3\" \
+                 refY=\"2\" orient=\"auto\"><path d=\"M0,0 L4,2 L0,4 z\" fill=\"{}\"/></marker>\
+                 </defs>\n",
+                self.options.arrows
+            ));
+        }
+        for element in &self.elements {
+            svg.push_str(element);
+            svg.push('\n');
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Board {
+    /// Draw the board as an SVG image, seen from `player`, or from Player0 if `player` is nobody.
+    /// See the [`svg`](crate::svg) module for the layout.
+    pub fn to_svg(&self, player: Player, options: &Options) -> String {
+        let player = match player {
+            Player::Nobody => Player::Player0,
+            player => player,
+        };
+        let mut canvas = Canvas::new(options);
+        canvas.board(self, player);
+        canvas.finish()
+    }
+}
+
+impl Game {
+    /// Draw the game as an SVG image, seen from the player on roll, with the cube, the dices and
+    /// an arrow for each checker move of `play`. The play is drawn for the player on roll and may
+    /// be empty. See the [`svg`](crate::svg) module for the layout.
+    pub fn to_svg(&self, play: &[CheckerMove], options: &Options) -> String {
+        let player = match self.who_plays {
+            Player::Nobody => Player::Player0,
+            player => player,
+        };
+        let mut canvas = Canvas::new(options);
+        canvas.board(&self.board, player);
+        canvas.cube(self.cube.value(), self.cube.owner(), player);
+        if self.result.is_none() && self.who_plays != Player::Nobody && !self.roll_first {
+            canvas.dices(self.dices.values, player);
+        }
+        canvas.arrows(&self.board, player, play);
+        canvas.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Roll, ScriptedDices};
+    use crate::Error;
+
+    #[test]
+    fn board_to_svg() {
+        let svg = Board::new().to_svg(Player::Player0, &Options::default());
+        assert_eq!(svg, SNAPSHOT);
+        assert_eq!(svg.matches("<polygon").count(), 24);
+        assert_eq!(svg.matches("<circle").count(), 30);
+        assert_eq!(svg.matches("fill=\"#f5f5f5\" stroke").count(), 15);
+    }
+
+    #[test]
+    fn board_to_svg_overflow_bar_and_off() -> Result<(), Error> {
+        let mut board = Board::new();
+        board.set(Player::Player0, 5, 2)?;
+        board.set_bar(Player::Player1, 1)?;
+        board.set_off(Player::Player1, 3)?;
+        let svg = board.to_svg(Player::Player0, &Options::default());
+        // seven checkers on the 6 point, five drawn and the amount written on the last one
+        assert!(svg.contains(
+            "<text x=\"370\" y=\"182\" font-family=\"sans-serif\" font-size=\"16\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#222222\">7</text>"
+        ));
+        assert!(svg.contains("<circle cx=\"330\" cy=\"204\" r=\"17\" fill=\"#222222\""));
+        assert_eq!(
+            svg.matches("width=\"40\" height=\"10\" fill=\"#222222\"")
+                .count(),
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn game_to_svg_orientation() {
+        let game = Game::new();
+        let right = game.to_svg(&[], &Options::default());
+        let left = game.to_svg(
+            &[],
+            &Options {
+                orientation: Orientation::HomeLeft,
+                ..Options::default()
+            },
+        );
+        assert_ne!(right, left);
+        // point 1 is drawn in the top right or top left corner
+        assert!(right.contains("<text x=\"570\" y=\"10\""));
+        assert!(left.contains("<text x=\"110\" y=\"10\""));
+        // the centered cube shows 64
+        assert!(right.contains("<rect x=\"7\" y=\"222\" width=\"36\" height=\"36\""));
+        assert!(left.contains("<rect x=\"637\" y=\"222\" width=\"36\" height=\"36\""));
+        assert!(right.contains(">64</text>"));
+    }
+
+    #[test]
+    fn game_to_svg_dices_and_arrows() -> Result<(), Error> {
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(3, 1)]))?;
+        let play = [
+            CheckerMove::new(Field::Point(7), Field::Point(4)),
+            CheckerMove::new(Field::Point(5), Field::Point(4)),
+        ];
+        let svg = game.to_svg(&play, &Options::default());
+        assert_eq!(svg, game.to_svg(&play, &Options::default()));
+        // two dices with four spots
+        assert_eq!(svg.matches("r=\"3\"").count(), 4);
+        assert_eq!(svg.matches("marker-end=\"url(#arrow)\"").count(), 2);
+        assert!(svg.contains("<marker id=\"arrow\""));
+        assert!(svg.contains(
+            "<line x1=\"250\" y1=\"110\" x2=\"410\" y2=\"38\" stroke=\"#d01010\" stroke-width=\"3\""
+        ));
+        assert!(svg.contains(
+            "<line x1=\"370\" y1=\"182\" x2=\"410\" y2=\"74\" stroke=\"#d01010\" stroke-width=\"3\""
+        ));
+
+        assert!(!Game::new()
+            .to_svg(&[], &Options::default())
+            .contains("<defs>"));
+        Ok(())
+    }
+
+    /// Snapshot of the starting position seen from Player0
+    const SNAPSHOT: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="680" height="480" viewBox="0 0 680 480">
+<rect x="0" y="0" width="680" height="480" fill="#5c3a1e" stroke="#000000"/>
+<rect x="70" y="20" width="240" height="440" fill="#e8d8b0" stroke="#000000"/>
+<rect x="350" y="20" width="240" height="440" fill="#e8d8b0" stroke="#000000"/>
+<rect x="610" y="20" width="50" height="198" fill="#e8d8b0" stroke="#000000"/>
+<rect x="610" y="262" width="50" height="198" fill="#e8d8b0" stroke="#000000"/>
+<polygon points="550,20 590,20 570,200" fill="#c8a165"/>
+<text x="570" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">1</text>
+<polygon points="510,20 550,20 530,200" fill="#7a3b12"/>
+<text x="530" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">2</text>
+<polygon points="470,20 510,20 490,200" fill="#c8a165"/>
+<text x="490" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">3</text>
+<polygon points="430,20 470,20 450,200" fill="#7a3b12"/>
+<text x="450" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">4</text>
+<polygon points="390,20 430,20 410,200" fill="#c8a165"/>
+<text x="410" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">5</text>
+<polygon points="350,20 390,20 370,200" fill="#7a3b12"/>
+<text x="370" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">6</text>
+<polygon points="270,20 310,20 290,200" fill="#c8a165"/>
+<text x="290" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">7</text>
+<polygon points="230,20 270,20 250,200" fill="#7a3b12"/>
+<text x="250" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">8</text>
+<polygon points="190,20 230,20 210,200" fill="#c8a165"/>
+<text x="210" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">9</text>
+<polygon points="150,20 190,20 170,200" fill="#7a3b12"/>
+<text x="170" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">10</text>
+<polygon points="110,20 150,20 130,200" fill="#c8a165"/>
+<text x="130" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">11</text>
+<polygon points="70,20 110,20 90,200" fill="#7a3b12"/>
+<text x="90" y="10" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">12</text>
+<polygon points="70,460 110,460 90,280" fill="#c8a165"/>
+<text x="90" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">13</text>
+<polygon points="110,460 150,460 130,280" fill="#7a3b12"/>
+<text x="130" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">14</text>
+<polygon points="150,460 190,460 170,280" fill="#c8a165"/>
+<text x="170" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">15</text>
+<polygon points="190,460 230,460 210,280" fill="#7a3b12"/>
+<text x="210" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">16</text>
+<polygon points="230,460 270,460 250,280" fill="#c8a165"/>
+<text x="250" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">17</text>
+<polygon points="270,460 310,460 290,280" fill="#7a3b12"/>
+<text x="290" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">18</text>
+<polygon points="350,460 390,460 370,280" fill="#c8a165"/>
+<text x="370" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">19</text>
+<polygon points="390,460 430,460 410,280" fill="#7a3b12"/>
+<text x="410" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">20</text>
+<polygon points="430,460 470,460 450,280" fill="#c8a165"/>
+<text x="450" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">21</text>
+<polygon points="470,460 510,460 490,280" fill="#7a3b12"/>
+<text x="490" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">22</text>
+<polygon points="510,460 550,460 530,280" fill="#c8a165"/>
+<text x="530" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">23</text>
+<polygon points="550,460 590,460 570,280" fill="#7a3b12"/>
+<text x="570" y="470" font-family="sans-serif" font-size="12" text-anchor="middle" dominant-baseline="central" fill="#f5f5f5">24</text>
+<circle cx="570" cy="38" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="570" cy="74" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="38" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="370" cy="74" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="370" cy="110" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="370" cy="146" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="370" cy="182" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="250" cy="38" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="250" cy="74" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="250" cy="110" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="90" cy="38" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="90" cy="74" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="90" cy="110" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="90" cy="146" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="90" cy="182" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="90" cy="442" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="90" cy="406" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="90" cy="370" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="90" cy="334" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="90" cy="298" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="250" cy="442" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="250" cy="406" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="250" cy="370" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="442" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="406" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="370" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="334" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="370" cy="298" r="17" fill="#222222" stroke="#000000"/>
+<circle cx="570" cy="442" r="17" fill="#f5f5f5" stroke="#000000"/>
+<circle cx="570" cy="406" r="17" fill="#f5f5f5" stroke="#000000"/>
+</svg>
+"##;
+}
@@ -0,0 +1,297 @@
+use crate::rules::{Board, Dices, Player};
+use crate::{Error, Game};
+
+/// Maximum cube value written to the XGID, as log2
+const MAX_CUBE: u32 = 10;
+
+/// Character for the amount of checkers of a player on a point, uppercase for Player0
+fn checkers_char(checkers: u8, player: Player) -> char {
+    match (checkers, player) {
+        (0, _) => '-',
+        (n, Player::Player0) => (b'A' + n - 1) as char,
+        (n, _) => (b'a' + n - 1) as char,
+    }
+}
+
+impl Game {
+    /// Get the eXtreme Gammon XGID of the game.
+    ///
+    /// The XGID is written from the perspective of Player0, who is the bottom player in XG and
+    /// whose checkers are written in uppercase letters. A turn starts with dices `00` and the
+    /// dices are replaced by `D` while a double is offered. Before the opening roll, Player0 is
+    /// written as the player on turn.
+    ///
+    /// ```
+    /// use backgammon::Game;
+    ///
+    /// let game = Game::new();
+    /// assert_eq!(
+    ///     game.xgid().unwrap(),
+    ///     "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:0:0:0:7:10"
+    /// );
+    /// ```
+    pub fn xgid(&self) -> Result<String, Error> {
+        if self.result.is_some() {
+            return Err(Error::GameEnded);
+        }
+
+        let mut position = ['-'; 26];
+        let player0 = self.board.checkers(Player::Player0)?;
+        let player1 = self.board.checkers(Player::Player1)?;
+        for field in 0..24 {
+            if player0[field] > 0 {
+                position[field + 1] = checkers_char(player0[field], Player::Player0);
+            }
+            if player1[field] > 0 {
+                position[24 - field] = checkers_char(player1[field], Player::Player1);
+            }
+        }
+        position[0] = checkers_char(player1[24], Player::Player1);
+        position[25] = checkers_char(player0[24], Player::Player0);
+
+        let owner = match self.cube.owner() {
+            Player::Player0 => 1,
+            Player::Player1 => -1,
+            Player::Nobody => 0,
+        };
+        let turn = match self.who_plays {
+            Player::Player1 => -1,
+            _ => 1,
+        };
+        let dices = if self.cube_received {
+            String::from("D")
+        } else if self.beaver_received && self.roll_first {
+            String::from("B")
+        } else if self.who_plays == Player::Nobody || self.roll_first {
+            String::from("00")
+        } else {
+            format!("{}{}", self.dices.values.0, self.dices.values.1)
+        };
+        let flags = if self.rules.money_play() {
+            self.rules.jacoby as u8 | (self.rules.beaver as u8) << 1
+        } else {
+            self.crawford as u8
+        };
+
+        Ok(format!(
+            "XGID={}:{}:{}:{}:{}:{}:{}:{}:{}:{}",
+            position.iter().collect::<String>(),
+            self.cube.value().trailing_zeros(),
+            owner,
+            turn,
+            dices,
+            self.score.0,
+            self.score.1,
+            flags,
+            self.rules.points,
+            MAX_CUBE
+        ))
+    }
+
+    /// Create a game from an eXtreme Gammon XGID, with or without the leading `XGID=`.
+    ///
+    /// The maximum cube value of the XGID is not enforced.
+    pub fn from_xgid(xgid: &str) -> Result<Self, Error> {
+        let xgid = xgid.trim();
+        let xgid = xgid.strip_prefix("XGID=").unwrap_or(xgid);
+        let fields: Vec<&str> = xgid.split(':').collect();
+        if fields.len() != 10 {
+            return Err(Error::XgidInvalid);
+        }
+        let number = |field: &str| field.parse::<i64>().map_err(|_| Error::XgidInvalid);
+
+        // position
+        let position: Vec<char> = fields[0].chars().collect();
+        if position.len() != 26 {
+            return Err(Error::XgidInvalid);
+        }
+        let mut player0 = [0; 25];
+        let mut player1 = [0; 25];
+        for (i, c) in position.into_iter().enumerate() {
+            let (checkers, player) = match c {
+                '-' => continue,
+                'A'..='O' => (c as u8 - b'A' + 1, Player::Player0),
+                'a'..='o' => (c as u8 - b'a' + 1, Player::Player1),
+                _ => return Err(Error::XgidInvalid),
+            };
+            match (i, player) {
+                (0, Player::Player1) => player1[24] = checkers,
+                (25, Player::Player0) => player0[24] = checkers,
+                (1..=24, Player::Player0) => player0[i - 1] = checkers,
+                (1..=24, _) => player1[24 - i] = checkers,
+                _ => return Err(Error::XgidInvalid),
+            }
+        }
+
+        let mut game = Game::new();
+        game.board = Board::from_checkers(&player0, &player1).map_err(|_| Error::XgidInvalid)?;
+
+        // cube
+        let cube = number(fields[1])?;
+        if !(0..=15).contains(&cube) {
+            return Err(Error::XgidInvalid);
+        }
+        game.cube.set(1 << cube)?;
+        game.cube.set_owner(match number(fields[2])? {
+            1 => Player::Player0,
+            -1 => Player::Player1,
+            0 => Player::Nobody,
+            _ => return Err(Error::XgidInvalid),
+        });
+
+        // turn and dices
+        game.who_plays = match number(fields[3])? {
+            1 => Player::Player0,
+            -1 => Player::Player1,
+            _ => return Err(Error::XgidInvalid),
+        };
+        match fields[4] {
+            "00" | "R" => game.roll_first = true,
+            "D" => {
+                game.roll_first = true;
+                game.cube_received = true;
+            }
+            "B" => {
+                game.roll_first = true;
+                game.beaver_received = true;
+            }
+            dices => {
+                let values: Vec<u8> = dices
+                    .chars()
+                    .map(|c| c.to_digit(10).unwrap_or(0) as u8)
+                    .collect();
+                if values.len() != 2 || values.iter().any(|v| !(1..=6).contains(v)) {
+                    return Err(Error::XgidInvalid);
+                }
+                game.dices = Dices::from_values((values[0], values[1]));
+            }
+        }
+
+        // score and rules
+        let score = (number(fields[5])?, number(fields[6])?);
+        let flags = number(fields[7])?;
+        let points = number(fields[8])?;
+        let _max_cube = number(fields[9])?;
+        if score.0 < 0 || score.1 < 0 || !(0..=3).contains(&flags) || points < 0 {
+            return Err(Error::XgidInvalid);
+        }
+        game.score = (score.0 as u32, score.1 as u32);
+        game.rules.points = points as u32;
+        if game.rules.money_play() {
+            game.rules.jacoby = flags & 1 == 1;
+            game.rules.beaver = flags & 2 == 2;
+        } else {
+            if flags > 1 {
+                return Err(Error::XgidInvalid);
+            }
+            game.crawford = flags == 1;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{CheckerMove, Field, Roll, ScriptedDices};
+
+    const START: &str = "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:0:0:0:7:10";
+
+    #[test]
+    fn xgid_start() -> Result<(), Error> {
+        assert_eq!(Game::new().xgid()?, START);
+
+        let game = Game::from_xgid(START)?;
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.who_plays, Player::Player0);
+        assert_eq!(game.cube.value(), 1);
+        assert_eq!(game.cube.owner(), Player::Nobody);
+        assert_eq!(game.rules.points, 7);
+        assert!(game.legal_plays().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn xgid_round_trip() -> Result<(), Error> {
+        let mut game = Game::new();
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(6, 4)]))?;
+        let _ = game.play(
+            Player::Player0,
+            &[
+                CheckerMove::new(Field::Point(23), Field::Point(17)),
+                CheckerMove::new(Field::Point(17), Field::Point(13)),
+            ],
+        )?;
+        let _ = game.roll_with(&mut ScriptedDices::new(vec![(5, 2)]))?;
+
+        let xgid = game.xgid()?;
+        assert_eq!(xgid, "XGID=-b----E-C---eEA--c-e----A-:0:0:-1:52:0:0:0:7:10");
+        let imported = Game::from_xgid(&xgid)?;
+        assert_eq!(imported.board, game.board);
+        assert_eq!(imported.who_plays, Player::Player1);
+        assert_eq!(imported.dices, game.dices);
+        assert_eq!(imported.legal_plays()?, game.legal_plays()?);
+        assert_eq!(imported.xgid()?, xgid);
+        Ok(())
+    }
+
+    #[test]
+    fn xgid_bar_and_cube() -> Result<(), Error> {
+        let xgid = "XGID=a-----C-C---eE---c-e---AAB:1:1:1:D:3:2:0:5:10";
+        let mut game = Game::from_xgid(xgid)?;
+        assert_eq!(game.board.get().bar, (2, 1));
+        assert_eq!(game.board.get().board[23], 1);
+        assert_eq!(game.board.get().board[22], 1);
+        assert_eq!(game.cube.value(), 2);
+        assert_eq!(game.cube.owner(), Player::Player0);
+        assert_eq!(game.score, (3, 2));
+        assert_eq!(game.xgid()?, xgid);
+
+        let _ = game.accept_double(Player::Player1)?;
+        assert_eq!(game.cube.value(), 4);
+        assert_eq!(game.cube.owner(), Player::Player1);
+        Ok(())
+    }
+
+    #[test]
+    fn xgid_flags() -> Result<(), Error> {
+        let game = Game::from_xgid("-b----E-C---eE---c-e----B-:0:0:1:00:0:0:3:0:10")?;
+        assert!(game.rules.money_play());
+        assert!(game.rules.jacoby);
+        assert!(game.rules.beaver);
+
+        let game = Game::from_xgid("-b----E-C---eE---c-e----B-:0:0:1:00:4:2:1:5:10")?;
+        assert!(game.crawford());
+        assert_eq!(
+            game.xgid()?,
+            "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:4:2:1:5:10"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn xgid_invalid() {
+        for xgid in [
+            "",
+            "XGID=",
+            "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:0:0:0:7",
+            "XGID=-b----E-C---eE---c-e----B:0:0:1:00:0:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:0:2:1:00:0:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:0:0:0:00:0:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:0:0:1:07:0:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:0:0:2:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:x:0:1:00:0:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----B-:0:0:1:00:-1:0:0:7:10",
+            "XGID=-b----E-C---eE---c-e----Z-:0:0:1:00:0:0:0:7:10",
+            "XGID=Ab----E-C---eE---c-e----B-:0:0:1:00:0:0:0:7:10",
+            "XGID=-P----E-C---eE---c-e----B-:0:0:1:00:0:0:0:7:10",
+        ] {
+            assert!(
+                matches!(Game::from_xgid(xgid), Err(Error::XgidInvalid)),
+                "{}",
+                xgid
+            );
+        }
+    }
+}